@@ -0,0 +1,77 @@
+use serde_json::{Number, Value};
+
+/// Arithmetic operators supported in filter operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithOp {
+    Add, // `+`
+    Sub, // `-`
+    Mul, // `*`
+    Div, // `/`
+    Rem, // `%`
+}
+
+impl ArithOp {
+    /// Applies the operator to two JSON values.
+    /// Integers stay exact while the result fits an `i64` (or `u64`), and fall back to `f64`
+    /// otherwise; division yields an integer only when it is exact. Returns `None` for
+    /// non-numbers, division by zero and non-finite results.
+    pub fn apply(self, a: &Value, b: &Value) -> Option<Value> {
+        let (Value::Number(x), Value::Number(y)) = (a, b) else {
+            return None;
+        };
+        self.apply_int(x, y)
+            .or_else(|| self.apply_float(x.as_f64()?, y.as_f64()?))
+    }
+
+    /// Exact integer arithmetic, in i64 and then in u64 for large non-negative values.
+    /// `None` when an operand is not an integer, on overflow or for an inexact division.
+    fn apply_int(self, x: &Number, y: &Number) -> Option<Value> {
+        if let (Some(x), Some(y)) = (x.as_i64(), y.as_i64()) {
+            let r = match self {
+                ArithOp::Add => x.checked_add(y),
+                ArithOp::Sub => x.checked_sub(y),
+                ArithOp::Mul => x.checked_mul(y),
+                ArithOp::Div if y != 0 && x.checked_rem(y) == Some(0) => x.checked_div(y),
+                ArithOp::Div => None,
+                ArithOp::Rem => x.checked_rem(y),
+            };
+            if let Some(r) = r {
+                return Some(Value::from(r));
+            }
+        }
+        let (x, y) = (x.as_u64()?, y.as_u64()?);
+        let r = match self {
+            ArithOp::Add => x.checked_add(y),
+            ArithOp::Sub => x.checked_sub(y),
+            ArithOp::Mul => x.checked_mul(y),
+            ArithOp::Div if y != 0 && x % y == 0 => Some(x / y),
+            ArithOp::Div => None,
+            ArithOp::Rem => x.checked_rem(y),
+        };
+        r.map(Value::from)
+    }
+
+    /// Floating-point arithmetic; `None` for division by zero and non-finite results.
+    fn apply_float(self, x: f64, y: f64) -> Option<Value> {
+        let r = match self {
+            ArithOp::Add => x + y,
+            ArithOp::Sub => x - y,
+            ArithOp::Mul => x * y,
+            ArithOp::Div | ArithOp::Rem if y == 0.0 => return None,
+            ArithOp::Div => x / y,
+            ArithOp::Rem => x % y,
+        };
+        Number::from_f64(r).map(Value::Number)
+    }
+}
+
+/// Negates a JSON number, keeping integers exact. Returns `None` for non-numbers.
+pub fn negate(v: &Value) -> Option<Value> {
+    let n = v.as_number()?;
+    if let Some(i) = n.as_i64() {
+        if let Some(neg) = i.checked_neg() {
+            return Some(Value::from(neg));
+        }
+    }
+    Number::from_f64(-n.as_f64()?).map(Value::Number)
+}
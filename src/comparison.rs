@@ -1,4 +1,120 @@
-use serde_json::Value;
+use serde_json::{Number, Value};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// Comparison operators supported in filter expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,  // `==`
+    Ne,  // `!=`
+    Lt,  // `<`
+    Lte, // `<=`
+    Gt,  // `>`
+    Gte, // `>=`
+}
+
+impl CmpOp {
+    /// Applies the operator to an ordering as produced by `cmp_values`
+    /// (-1 if `a` < `b`, 0 if equal, 1 if `a` > `b`).
+    pub fn holds(self, ord: i32) -> bool {
+        match self {
+            CmpOp::Eq => ord == 0,
+            CmpOp::Ne => ord != 0,
+            CmpOp::Lt => ord < 0,
+            CmpOp::Lte => ord <= 0,
+            CmpOp::Gt => ord > 0,
+            CmpOp::Gte => ord >= 0,
+        }
+    }
+}
+
+/// How comparisons in pragmatic filters treat values of different types.
+/// RFC 9535 queries always use the RFC rules, which `Strict` follows as well.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ComparisonMode {
+    /// Numbers and numeric strings are compared as numbers, and other mixed types by their
+    /// JSON text (the historical behaviour).
+    #[default]
+    Lenient,
+    /// No coercion: values of different types are never equal, and ordering is only
+    /// defined between two numbers or two strings.
+    Strict,
+    /// Like `Strict`, except that strings holding a JSON number compare as that number.
+    NumericStrings,
+}
+
+/// Parses a comparison mode name: `lenient`, `strict` or `numeric_strings`.
+impl FromStr for ComparisonMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lenient" => Ok(ComparisonMode::Lenient),
+            "strict" => Ok(ComparisonMode::Strict),
+            "numeric_strings" => Ok(ComparisonMode::NumericStrings),
+            other => Err(format!("unknown comparison mode `{other}`")),
+        }
+    }
+}
+
+/// Compares two values with the given operator under a comparison mode.
+pub fn compare_values(op: CmpOp, a: &Value, b: &Value, mode: ComparisonMode) -> bool {
+    match mode {
+        ComparisonMode::Lenient => cmp_values(a, b, |o| op.holds(o)),
+        ComparisonMode::Strict => rfc_compare(op, Some(a), Some(b)),
+        ComparisonMode::NumericStrings => rfc_compare(op, Some(&as_number(a)), Some(&as_number(b))),
+    }
+}
+
+/// Turns a string holding a JSON number into that number; other values are kept.
+fn as_number(v: &Value) -> Cow<'_, Value> {
+    match v {
+        Value::String(s) => match s.parse::<Number>() {
+            Ok(n) => Cow::Owned(Value::Number(n)),
+            Err(_) => Cow::Borrowed(v),
+        },
+        _ => Cow::Borrowed(v),
+    }
+}
+
+/// Membership and containment operators supported in filter expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOp {
+    In,       // `in`: the value is an element of the array
+    Nin,      // `nin`: the value is not an element of the array
+    Contains, // `contains`: the array has the element, or the string has the substring
+    SubsetOf, // `subsetof`: every element of the array is in the other array
+    AnyOf,    // `anyof`: the arrays share at least one element
+    NoneOf,   // `noneof`: the arrays share no element
+}
+
+impl SetOp {
+    /// Applies the operator to `a` (left) and `b` (right). Elements are matched with the
+    /// same equality as `==` under `mode`; operands of the wrong type never match.
+    pub fn holds(self, a: &Value, b: &Value, mode: ComparisonMode) -> bool {
+        let has_element =
+            |arr: &[Value], v: &Value| arr.iter().any(|x| compare_values(CmpOp::Eq, x, v, mode));
+        match (self, a, b) {
+            (SetOp::In, _, Value::Array(arr)) => has_element(arr, a),
+            (SetOp::Nin, _, Value::Array(arr)) => !has_element(arr, a),
+            (SetOp::Contains, Value::Array(arr), _) => has_element(arr, b),
+            (SetOp::Contains, Value::String(s), Value::String(needle)) => {
+                s.contains(needle.as_str())
+            }
+            (SetOp::SubsetOf, Value::Array(xa), Value::Array(xb)) => {
+                xa.iter().all(|x| has_element(xb, x))
+            }
+            (SetOp::AnyOf, Value::Array(xa), Value::Array(xb)) => {
+                xa.iter().any(|x| has_element(xb, x))
+            }
+            (SetOp::NoneOf, Value::Array(xa), Value::Array(xb)) => {
+                !xa.iter().any(|x| has_element(xb, x))
+            }
+            _ => false,
+        }
+    }
+}
 
 /// Compares two `serde_json::Value` instances using a provided predicate on their ordering.
 /// The comparison is case-sensitive for strings and attempts to handle numbers, booleans, and mixed types.
@@ -7,7 +123,7 @@ use serde_json::Value;
 /// * `a` - First value to compare.
 /// * `b` - Second value to compare.
 /// * `pred_on_ord` - A predicate function that takes an `i32` representing the ordering:
-///     -1 if `a` < `b`, 0 if `a` == `b`, 1 if `a` > `b`.
+///   -1 if `a` < `b`, 0 if `a` == `b`, 1 if `a` > `b`.
 ///
 /// # Returns
 /// * `bool` - Result of the predicate applied to the comparison.
@@ -20,23 +136,11 @@ where
         // Both are strings: compare lexicographically (case-sensitive)
         (Value::String(sa), Value::String(sb)) => pred_on_ord(sa.cmp(sb) as i32),
 
-        // Both are numbers: compare as f64 if possible, otherwise fallback to equality
-        (Value::Number(na), Value::Number(nb)) => {
-            if let (Some(da), Some(db)) = (na.as_f64(), nb.as_f64()) {
-                // Use epsilon to check for floating-point equality
-                let ord = if (da - db).abs() < f64::EPSILON {
-                    0
-                } else if da < db {
-                    -1
-                } else {
-                    1
-                };
-                pred_on_ord(ord)
-            } else {
-                // Fallback: compare as JSON numbers (rare case)
-                pred_on_ord(0) && na == nb
-            }
-        }
+        // Both are numbers: compare exactly, without rounding through f64
+        (Value::Number(na), Value::Number(nb)) => match number_cmp(na, nb) {
+            Some(ord) => pred_on_ord(ord as i32),
+            None => false,
+        },
 
         // Both are booleans: compare by casting to i32 (false=0, true=1)
         (Value::Bool(ba), Value::Bool(bb)) => {
@@ -44,20 +148,25 @@ where
             pred_on_ord(ord)
         }
 
-        // One is a number, one is a string: try to parse string as f64 and compare
-        (Value::Number(na), Value::String(sb)) | (Value::String(sb), Value::Number(na)) => {
-            if let (Some(da), Ok(db)) = (na.as_f64(), sb.parse::<f64>()) {
-                let ord = if (da - db).abs() < f64::EPSILON {
-                    0
-                } else if da < db {
-                    -1
-                } else {
-                    1
-                };
-                pred_on_ord(ord)
+        // One is a number, one is a string: try to parse the string as a number and compare
+        (Value::Number(na), Value::String(sb)) => match parse_number(sb) {
+            Some(nb) => number_cmp(na, &nb).is_some_and(|o| pred_on_ord(o as i32)),
+            // Fallback: compare their string representations
+            None => pred_on_ord(a.to_string().cmp(&b.to_string()) as i32),
+        },
+        (Value::String(sa), Value::Number(nb)) => match parse_number(sa) {
+            Some(na) => number_cmp(&na, nb).is_some_and(|o| pred_on_ord(o as i32)),
+            None => pred_on_ord(a.to_string().cmp(&b.to_string()) as i32),
+        },
+
+        // Both are arrays or both are objects: deep equality, with member order and number
+        // formatting ignored; unequal values are ordered by their JSON text
+        (Value::Array(_), Value::Array(_)) | (Value::Object(_), Value::Object(_)) => {
+            if json_eq(a, b) {
+                pred_on_ord(0)
             } else {
-                // Fallback: compare their string representations
-                pred_on_ord(a.to_string().cmp(&b.to_string()) as i32)
+                let ord = a.to_string().cmp(&b.to_string()) as i32;
+                pred_on_ord(if ord == 0 { 1 } else { ord })
             }
         }
 
@@ -65,3 +174,203 @@ where
         _ => pred_on_ord(a.to_string().cmp(&b.to_string()) as i32),
     }
 }
+
+/// Applies Unicode default case folding to a string, and to the strings nested in an array
+/// or object (member names are kept), so that folded values compare case-insensitively.
+pub fn fold_case(v: Value) -> Value {
+    match v {
+        Value::String(s) => Value::String(caseless::default_case_fold_str(&s)),
+        Value::Array(items) => Value::Array(items.into_iter().map(fold_case).collect()),
+        Value::Object(map) => {
+            Value::Object(map.into_iter().map(|(k, v)| (k, fold_case(v))).collect())
+        }
+        other => other,
+    }
+}
+
+/// Compares two filter operands following RFC 9535 section 2.3.5.2.2.
+/// `None` stands for an empty nodelist ("Nothing"), which only equals another `None`.
+/// Ordering operators are false unless both sides are numbers or both are strings.
+pub fn rfc_compare(op: CmpOp, a: Option<&Value>, b: Option<&Value>) -> bool {
+    match op {
+        CmpOp::Eq => rfc_eq(a, b),
+        CmpOp::Ne => !rfc_eq(a, b),
+        CmpOp::Lt => rfc_lt(a, b),
+        CmpOp::Lte => rfc_lt(a, b) || rfc_eq(a, b),
+        CmpOp::Gt => rfc_lt(b, a),
+        CmpOp::Gte => rfc_lt(b, a) || rfc_eq(a, b),
+    }
+}
+
+/// RFC 9535 equality: Nothing equals only Nothing, otherwise deep JSON equality.
+fn rfc_eq(a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => json_eq(a, b),
+        _ => false,
+    }
+}
+
+/// RFC 9535 less-than: defined for number pairs and string pairs only.
+fn rfc_lt(a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a, b) {
+        (Some(Value::Number(na)), Some(Value::Number(nb))) => {
+            number_cmp(na, nb) == Some(Ordering::Less)
+        }
+        // Byte order of UTF-8 strings is Unicode scalar value order
+        (Some(Value::String(sa)), Some(Value::String(sb))) => sa < sb,
+        _ => false,
+    }
+}
+
+/// Deep equality of two JSON values where numbers compare by value (`1 == 1.0`).
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(na), Value::Number(nb)) => number_cmp(na, nb) == Some(Ordering::Equal),
+        (Value::Array(xa), Value::Array(xb)) => {
+            xa.len() == xb.len() && xa.iter().zip(xb).all(|(x, y)| json_eq(x, y))
+        }
+        (Value::Object(ma), Value::Object(mb)) => {
+            ma.len() == mb.len()
+                && ma
+                    .iter()
+                    .all(|(k, x)| mb.get(k).is_some_and(|y| json_eq(x, y)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Parses a string as a number for the lenient comparison of numbers and numeric strings.
+/// Besides JSON numbers this accepts the other forms Rust reads as `f64` (e.g. `+1`, `.5`).
+fn parse_number(s: &str) -> Option<Number> {
+    s.parse::<Number>()
+        .ok()
+        .or_else(|| Number::from_f64(s.parse().ok()?))
+}
+
+/// Orders two JSON numbers exactly: integers (`i64` or `u64`) and floats are compared by
+/// value without rounding either side, so `9007199254740993 > 9007199254740992.0`.
+/// With the `arbitrary_precision` feature, numbers are compared as decimals as written.
+pub(crate) fn number_cmp(a: &Number, b: &Number) -> Option<Ordering> {
+    #[cfg(feature = "arbitrary_precision")]
+    if let Some(ord) = decimal::cmp(a, b) {
+        return Some(ord);
+    }
+    match (integer(a), integer(b)) {
+        (Some(x), Some(y)) => Some(x.cmp(&y)),
+        (Some(x), None) => int_float_cmp(x, b.as_f64()?),
+        (None, Some(y)) => int_float_cmp(y, a.as_f64()?).map(Ordering::reverse),
+        (None, None) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+    }
+}
+
+/// The value of an integer number; `i128` holds every `i64` and `u64`.
+fn integer(n: &Number) -> Option<i128> {
+    n.as_i64()
+        .map(i128::from)
+        .or_else(|| n.as_u64().map(i128::from))
+}
+
+/// Orders an integer against a float without converting the integer to `f64`.
+fn int_float_cmp(x: i128, f: f64) -> Option<Ordering> {
+    if f.is_nan() {
+        return None;
+    }
+    // Beyond ±2^127 the float is out of range of any integer we hold
+    if f >= 2f64.powi(127) {
+        return Some(Ordering::Less);
+    }
+    if f < -(2f64.powi(127)) {
+        return Some(Ordering::Greater);
+    }
+    // The integral part of a float in range converts to i128 exactly
+    let whole = f.trunc();
+    Some(x.cmp(&(whole as i128)).then_with(|| {
+        if f > whole {
+            Ordering::Less
+        } else if f < whole {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }))
+}
+
+/// Exact comparison of numbers kept as written (the `arbitrary_precision` feature of
+/// serde_json), e.g. `0.10000000000000000001 > 0.1`.
+#[cfg(feature = "arbitrary_precision")]
+mod decimal {
+    use serde_json::Number;
+    use std::cmp::Ordering;
+
+    /// A decimal `sign * 0.digits * 10^exp` with no leading or trailing zero digits;
+    /// zero has sign 0 and no digits.
+    #[derive(PartialEq, Eq)]
+    struct Decimal {
+        sign: i8,
+        exp: i64,
+        digits: Vec<u8>,
+    }
+
+    impl Decimal {
+        /// Parses a number in JSON syntax (also accepting a leading `+` in the exponent).
+        fn parse(s: &str) -> Option<Decimal> {
+            let (negative, s) = match s.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, s),
+            };
+            let (mantissa, exp) = match s.find(['e', 'E']) {
+                Some(i) => (&s[..i], s[i + 1..].parse::<i64>().ok()?),
+                None => (s, 0),
+            };
+            let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+            if int.is_empty() || !int.bytes().chain(frac.bytes()).all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let all: Vec<u8> = int.bytes().chain(frac.bytes()).collect();
+            let leading = all.iter().take_while(|&&c| c == b'0').count();
+            let digits: Vec<u8> = all[leading..].to_vec();
+            let trailing = digits.iter().rev().take_while(|&&c| c == b'0').count();
+            let digits = digits[..digits.len() - trailing].to_vec();
+            if digits.is_empty() {
+                return Some(Decimal {
+                    sign: 0,
+                    exp: 0,
+                    digits,
+                });
+            }
+            let exp = exp.checked_add(int.len() as i64 - leading as i64)?;
+            Some(Decimal {
+                sign: if negative { -1 } else { 1 },
+                exp,
+                digits,
+            })
+        }
+    }
+
+    impl Ord for Decimal {
+        fn cmp(&self, other: &Self) -> Ordering {
+            let magnitude = self
+                .exp
+                .cmp(&other.exp)
+                .then_with(|| self.digits.cmp(&other.digits));
+            match self.sign.cmp(&other.sign) {
+                Ordering::Equal if self.sign < 0 => magnitude.reverse(),
+                Ordering::Equal if self.sign == 0 => Ordering::Equal,
+                Ordering::Equal => magnitude,
+                ord => ord,
+            }
+        }
+    }
+
+    impl PartialOrd for Decimal {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    /// Compares two numbers by their decimal text; `None` if either cannot be read.
+    pub(super) fn cmp(a: &Number, b: &Number) -> Option<Ordering> {
+        Some(Decimal::parse(&a.to_string())?.cmp(&Decimal::parse(&b.to_string())?))
+    }
+}
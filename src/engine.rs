@@ -1,25 +1,28 @@
-use crate::{expression, jsonpath};
+use crate::expression;
+use crate::jsonpath::{self, PathMode, QueryOptions};
 use itertools::Itertools;
 use serde_json::Value;
+use std::collections::HashMap;
 
-/// =========================
-/// Public API (Expression)
-/// =========================
+// =========================
+// Public API (Expression)
+// =========================
 
 /// Evaluates a single expression string.
 /// Example: first(from_json("<JSON>", "$.path"))
 /// Returns the result as a serde_json::Value.
-/// If parsing fails, returns Value::Null.
+/// If parsing fails or a variable is unbound, returns Value::Null.
 pub fn eval_expr(expr: &str) -> Value {
     match expression::parse_expr(expr) {
-        Ok(ast) => expression::eval_ast(&ast), // Evaluate parsed AST
-        Err(_) => Value::Null,                 // Return Null on parse error
+        Ok(ast) if expression::unbound_var(&ast, &HashMap::new()).is_some() => Value::Null,
+        Ok(ast) => expression::eval_ast(&ast, &mut expression::Env::default()), // Evaluate parsed AST
+        Err(_) => Value::Null, // Return Null on parse error
     }
 }
 
-/// =========================
-/// Public API (Library funcs)
-/// =========================
+// =========================
+// Public API (Library funcs)
+// =========================
 
 /// Parses a JSON string and evaluates a JSONPath expression.
 /// Returns an array of matches, or Null if JSON is invalid or no match found.
@@ -31,6 +34,44 @@ pub fn from_json(json_str: &str, path: &str) -> Value {
     jsonpath::from_value(&data, path) // Apply JSONPath to parsed data
 }
 
+/// Evaluates a JSONPath expression against an already parsed value, without serializing
+/// and re-parsing it. Returns an array of matches, or Null if no match is found.
+pub fn query(data: &Value, path: &str) -> Value {
+    jsonpath::from_value(data, path)
+}
+
+/// Parses a JSON string and evaluates a JSONPath expression in the given dialect.
+/// In `PathMode::Rfc9535` the result is always an array of matches (possibly empty),
+/// and Null means the JSON or the path is invalid.
+pub fn from_json_with_mode(json_str: &str, path: &str, mode: PathMode) -> Value {
+    let data: Value = match serde_json::from_str(json_str) {
+        Ok(v) => v,                   // Successfully parsed JSON
+        Err(_) => return Value::Null, // Return Null on parse error
+    };
+    from_value_with_mode(&data, path, mode)
+}
+
+/// Evaluates a JSONPath expression in the given dialect against an already parsed value.
+pub fn from_value_with_mode(data: &Value, path: &str, mode: PathMode) -> Value {
+    jsonpath::from_value_with_mode(data, path, mode)
+}
+
+/// Parses a JSON string and evaluates a JSONPath expression with the given query options
+/// (dialect, case-insensitive comparisons).
+pub fn from_json_with_options(json_str: &str, path: &str, opts: QueryOptions) -> Value {
+    let data: Value = match serde_json::from_str(json_str) {
+        Ok(v) => v,                   // Successfully parsed JSON
+        Err(_) => return Value::Null, // Return Null on parse error
+    };
+    from_value_with_options(&data, path, opts)
+}
+
+/// Evaluates a JSONPath expression with the given query options against an already parsed
+/// value.
+pub fn from_value_with_options(data: &Value, path: &str, opts: QueryOptions) -> Value {
+    jsonpath::from_value_with_options(data, path, opts)
+}
+
 /// Returns the first element from a result array.
 /// If input is not an array or is empty, returns Null.
 pub fn first(vals: &Value) -> Value {
@@ -63,6 +104,12 @@ pub fn unique(vals: &Value) -> Value {
 pub fn or_default(vals: &Value, default_json: &str) -> Value {
     let default_val = serde_json::from_str::<Value>(default_json)
         .unwrap_or_else(|_| Value::String(default_json.to_string())); // Fallback to string if not valid JSON
+    or_default_value(vals, default_val)
+}
+
+/// Returns the given default value if input is Null or an empty array.
+/// Otherwise, returns the original value.
+pub fn or_default_value(vals: &Value, default_val: Value) -> Value {
     match vals {
         Value::Null => default_val,                     // Use default if Null
         Value::Array(a) if a.is_empty() => default_val, // Use default if empty array
@@ -107,7 +154,7 @@ mod tests {
         assert_eq!(out, json!("nexa-agent-server"));
     }
 
-/// Tests that `unique` removes duplicate values.
+    /// Tests that `unique` removes duplicate values.
     #[test]
     fn unique_deduplicates() {
         let json = r#"{"a":[1,1,2,2,3]}"#;
@@ -1,17 +1,71 @@
 // src/expression.rs
 
 // Import required modules and functions from other files
+use crate::engine::from_value_with_options;
+use crate::jsonpath::QueryOptions;
 use crate::parser::{ParseError, Parser};
-use crate::{first, from_json, or_default, unique};
-use serde_json::Value;
+use crate::{first, from_json_with_options, or_default, or_default_value, unique};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
 
 /// Enum representing an expression node in the AST.
 /// - `Call`: Function call with a name and arguments.
 /// - `Str`: String literal.
+/// - `Num`, `Bool`, `Null`: JSON scalar literals.
+/// - `Array`, `Object`: JSON literals whose elements are expressions.
+/// - `Let`: binds the value of an expression to a name while evaluating `body`.
+/// - `Var`: a `$name` variable reference.
 #[derive(Debug, Clone)]
 pub enum ENode {
-    Call { name: String, args: Vec<ENode> },
+    Call {
+        name: String,
+        args: Vec<ENode>,
+    },
     Str(String),
+    Num(Number),
+    Bool(bool),
+    Null,
+    Array(Vec<ENode>),
+    Object(Vec<(String, ENode)>),
+    Let {
+        name: String,
+        value: Box<ENode>,
+        body: Box<ENode>,
+    },
+    Var(String),
+}
+
+/// The environment an expression is evaluated in: default query options for from_json and
+/// the variables in scope.
+#[derive(Debug, Clone, Default)]
+pub struct Env<'v> {
+    /// Query options used by from_json; its third argument applies on top.
+    defaults: QueryOptions,
+    /// Variables supplied by the caller, visible everywhere unless shadowed by `let`.
+    globals: Option<&'v HashMap<String, Value>>,
+    /// Let-bound variables, innermost last so that later bindings shadow earlier ones.
+    vars: Vec<(String, Value)>,
+}
+
+impl<'v> Env<'v> {
+    /// Creates an environment with the given external variables.
+    pub fn new(defaults: QueryOptions, globals: &'v HashMap<String, Value>) -> Self {
+        Env {
+            defaults,
+            globals: Some(globals),
+            vars: Vec::new(),
+        }
+    }
+
+    /// Looks up a variable, innermost binding first, then among the external variables.
+    fn lookup(&self, name: &str) -> Option<&Value> {
+        self.vars
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
+            .or_else(|| self.globals?.get(name))
+    }
 }
 
 /// Type alias for parse errors.
@@ -21,7 +75,7 @@ pub type EParseErr = ParseError;
 /// Returns an error if parsing fails or if there is trailing input.
 pub fn parse_expr(input: &str) -> Result<ENode, EParseErr> {
     let mut p = EParser::new(input);
-    let node = p.parse_node()?; // Parse the main node
+    let node = p.parse_let()?; // Parse the main node
     p.skip_ws(); // Skip any trailing whitespace
     if !p.eof() {
         // If there is extra input, return an error
@@ -43,32 +97,143 @@ impl<'a> EParser<'a> {
         }
     }
 
-    /// Parses a single AST node (either a string or a function call).
+    /// Parses `let name = expr; ...` bindings followed by the expression that uses them.
+    fn parse_let(&mut self) -> Result<ENode, EParseErr> {
+        self.parser.skip_ws();
+        let start = self.parser.pos();
+        if self.parser.parse_identifier().ok().as_deref() == Some("let") {
+            self.parser.skip_ws();
+            // `let(...)` would be a function call
+            if self.parser.peek_char() != Some('(') {
+                let name = self.parser.parse_identifier()?;
+                self.parser.skip_ws();
+                self.parser.expect('=')?;
+                let value = self.parse_pipeline()?;
+                self.parser.skip_ws();
+                self.parser.expect(';')?;
+                let body = self.parse_let()?;
+                return Ok(ENode::Let {
+                    name,
+                    value: Box::new(value),
+                    body: Box::new(body),
+                });
+            }
+        }
+        self.parser.set_pos(start);
+        self.parse_pipeline()
+    }
+
+    /// Parses a pipeline `expr | call(args) | ...`, desugared so that each left-hand value
+    /// becomes the first argument of the call on its right: `a | f(b)` is `f(a, b)`.
+    fn parse_pipeline(&mut self) -> Result<ENode, EParseErr> {
+        let mut node = self.parse_node()?;
+        loop {
+            self.parser.skip_ws();
+            if !self.parser.consume_char('|') {
+                return Ok(node);
+            }
+            match self.parse_node()? {
+                ENode::Call { name, mut args } => {
+                    args.insert(0, node);
+                    node = ENode::Call { name, args };
+                }
+                _ => {
+                    return Err(EParseErr::InvalidSyntax(
+                        "the right-hand side of `|` must be a function call".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Parses a single AST node (a literal or a function call).
     fn parse_node(&mut self) -> Result<ENode, EParseErr> {
         self.parser.skip_ws();
         // If the next character is a quote, parse a string literal
         if self.parser.peek_char() == Some('"') || self.parser.peek_char() == Some('\'') {
             return Ok(ENode::Str(self.parser.parse_quoted_string()?));
         }
-        // Otherwise, parse a function call: name(args)
+        // Number literal, in JSON syntax
+        if matches!(self.parser.peek_char(), Some(c) if c == '-' || c.is_ascii_digit()) {
+            return match self.parser.parse_json_number()? {
+                Value::Number(n) => Ok(ENode::Num(n)),
+                _ => Err(EParseErr::InvalidSyntax("number expected".into())),
+            };
+        }
+        // Variable reference: $name
+        if self.parser.consume_char('$') {
+            return Ok(ENode::Var(self.parser.parse_identifier()?));
+        }
+        // Array literal: [expr, ...]
+        if self.parser.consume_char('[') {
+            let items = self.parse_list(']')?;
+            self.parser.expect(']')?;
+            return Ok(ENode::Array(items));
+        }
+        // Object literal: {"key": expr, ...}
+        if self.parser.consume_char('{') {
+            let members = self.parse_members()?;
+            self.parser.expect('}')?;
+            return Ok(ENode::Object(members));
+        }
+        // Otherwise, parse a function call: name(args), or a true/false/null literal
         let name = self.parser.parse_identifier()?;
         self.parser.skip_ws();
+        if self.parser.peek_char() != Some('(') {
+            match name.as_str() {
+                "true" => return Ok(ENode::Bool(true)),
+                "false" => return Ok(ENode::Bool(false)),
+                "null" => return Ok(ENode::Null),
+                _ => {}
+            }
+        }
         self.parser.expect('(')?; // Expect opening parenthesis
         let args = self.parse_args()?; // Parse arguments
         self.parser.expect(')')?; // Expect closing parenthesis
         Ok(ENode::Call { name, args })
     }
 
+    /// Parses the comma-separated members of an object literal, up to the closing brace.
+    fn parse_members(&mut self) -> Result<Vec<(String, ENode)>, EParseErr> {
+        let mut out = Vec::new();
+        self.parser.skip_ws();
+        if self.parser.peek_char() == Some('}') {
+            return Ok(out);
+        }
+        loop {
+            self.parser.skip_ws();
+            if !matches!(self.parser.peek_char(), Some('"') | Some('\'')) {
+                return Err(EParseErr::InvalidSyntax(
+                    "object key must be a string".into(),
+                ));
+            }
+            let key = self.parser.parse_quoted_string()?;
+            self.parser.skip_ws();
+            self.parser.expect(':')?;
+            out.push((key, self.parse_pipeline()?));
+            self.parser.skip_ws();
+            if !self.parser.consume_char(',') {
+                break;
+            }
+        }
+        Ok(out)
+    }
+
     /// Parses a comma-separated list of arguments for a function call.
     fn parse_args(&mut self) -> Result<Vec<ENode>, EParseErr> {
+        self.parse_list(')')
+    }
+
+    /// Parses a comma-separated list of nodes, up to (not including) `close`.
+    fn parse_list(&mut self, close: char) -> Result<Vec<ENode>, EParseErr> {
         let mut out = Vec::new();
         self.parser.skip_ws();
-        // If the next character is a closing parenthesis, there are no arguments
-        if self.parser.peek_char() == Some(')') {
+        // If the next character closes the list, it is empty
+        if self.parser.peek_char() == Some(close) {
             return Ok(out);
         }
         loop {
-            let node = self.parse_node()?; // Parse each argument node
+            let node = self.parse_pipeline()?; // Parse each argument node
             out.push(node);
             self.parser.skip_ws();
             // If a comma is found, continue parsing more arguments
@@ -106,54 +271,134 @@ fn extract_string(value: Value) -> Option<String> {
     }
 }
 
+/// Resolves the query options of from_json and query: the environment's defaults, with the
+/// optional third argument (e.g. "rfc9535" or "ignore_case") applied on top.
+/// Returns None when that argument is not a valid options string.
+fn query_options(args: &[ENode], env: &mut Env<'_>) -> Option<QueryOptions> {
+    match args.get(2) {
+        None => Some(env.defaults),
+        Some(arg) => {
+            let s = extract_string(eval_ast(arg, env))?;
+            env.defaults.apply_str(&s).ok()
+        }
+    }
+}
+
+/// Finds the first `$name` that no enclosing `let` and no external variable binds.
+/// Scoping is lexical, so this is checked before evaluation.
+pub fn unbound_var<'a>(node: &'a ENode, globals: &HashMap<String, Value>) -> Option<&'a str> {
+    fn walk<'a>(
+        node: &'a ENode,
+        scope: &mut Vec<&'a str>,
+        globals: &HashMap<String, Value>,
+    ) -> Option<&'a str> {
+        match node {
+            ENode::Let { name, value, body } => walk(value, scope, globals).or_else(|| {
+                scope.push(name);
+                let out = walk(body, scope, globals);
+                scope.pop();
+                out
+            }),
+            ENode::Var(name) if !scope.contains(&name.as_str()) && !globals.contains_key(name) => {
+                Some(name)
+            }
+            ENode::Array(items) | ENode::Call { args: items, .. } => {
+                items.iter().find_map(|n| walk(n, scope, globals))
+            }
+            ENode::Object(members) => members.iter().find_map(|(_, n)| walk(n, scope, globals)),
+            _ => None,
+        }
+    }
+    walk(node, &mut Vec::new(), globals)
+}
+
 /// Evaluates an AST node and returns a JSON value.
-/// Supports built-in functions: from_json, first, unique, or_default.
-pub fn eval_ast(node: &ENode) -> Value {
+/// Supports built-in functions: from_json, query, first, unique, or_default.
+/// Variables must be bound (see `unbound_var`); an unbound one evaluates to null.
+pub fn eval_ast(node: &ENode, env: &mut Env<'_>) -> Value {
     match node {
+        // Evaluate the bound value once, then the body with the binding in scope
+        ENode::Let { name, value, body } => {
+            let v = eval_ast(value, env);
+            env.vars.push((name.clone(), v));
+            let out = eval_ast(body, env);
+            env.vars.pop();
+            out
+        }
+        ENode::Var(name) => env.lookup(name).cloned().unwrap_or(Value::Null),
         // If the node is a string, return it as a JSON string
         ENode::Str(s) => Value::String(s.clone()),
+        // Other literals evaluate to the matching JSON value
+        ENode::Num(n) => Value::Number(n.clone()),
+        ENode::Bool(b) => Value::Bool(*b),
+        ENode::Null => Value::Null,
+        ENode::Array(items) => items.iter().map(|n| eval_ast(n, env)).collect(),
+        ENode::Object(members) => Value::Object(
+            members
+                .iter()
+                .map(|(k, n)| (k.clone(), eval_ast(n, env)))
+                .collect::<Map<_, _>>(),
+        ),
         // If the node is a function call, match the function name
         ENode::Call { name, args } => match name.as_str() {
             "from_json" => {
-                // from_json(json_string, path_string)
-                if !check_arg_count(args, 2) {
+                // from_json(json_string, path_string[, mode_string])
+                if !check_arg_count(args, 2) && !check_arg_count(args, 3) {
                     return Value::Null;
                 }
-                let json_s = match extract_string(eval_ast(&args[0])) {
+                let json_s = match extract_string(eval_ast(&args[0], env)) {
                     Some(s) => s,
                     None => return Value::Null,
                 };
-                let path_s = match extract_string(eval_ast(&args[1])) {
+                let path_s = match extract_string(eval_ast(&args[1], env)) {
                     Some(s) => s,
                     None => return Value::Null,
                 };
-                from_json(&json_s, &path_s)
+                match query_options(args, env) {
+                    Some(opts) => from_json_with_options(&json_s, &path_s, opts),
+                    None => Value::Null,
+                }
+            }
+            "query" => {
+                // query(value, path_string[, mode_string]): like from_json on a value
+                if !check_arg_count(args, 2) && !check_arg_count(args, 3) {
+                    return Value::Null;
+                }
+                let data = eval_ast(&args[0], env);
+                let path_s = match extract_string(eval_ast(&args[1], env)) {
+                    Some(s) => s,
+                    None => return Value::Null,
+                };
+                match query_options(args, env) {
+                    Some(opts) => from_value_with_options(&data, &path_s, opts),
+                    None => Value::Null,
+                }
             }
             "first" => {
                 // first(array)
                 if !check_arg_count(args, 1) {
                     return Value::Null;
                 }
-                first(&eval_ast(&args[0]))
+                first(&eval_ast(&args[0], env))
             }
             "unique" => {
                 // unique(array)
                 if !check_arg_count(args, 1) {
                     return Value::Null;
                 }
-                unique(&eval_ast(&args[0]))
+                unique(&eval_ast(&args[0], env))
             }
             "or_default" => {
-                // or_default(value, default_string)
+                // or_default(value, default): a string default is parsed as JSON, any
+                // other literal is used as is
                 if !check_arg_count(args, 2) {
                     return Value::Null;
                 }
-                let v = eval_ast(&args[0]);
-                let d = match extract_string(eval_ast(&args[1])) {
-                    Some(s) => s,
-                    None => return Value::Null,
-                };
-                or_default(&v, &d)
+                let v = eval_ast(&args[0], env);
+                match eval_ast(&args[1], env) {
+                    Value::String(d) => or_default(&v, &d),
+                    d => or_default_value(&v, d),
+                }
             }
             // Unknown function name, return null
             _ => Value::Null,
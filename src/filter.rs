@@ -1,51 +1,90 @@
-use crate::comparison::cmp_values;
+use crate::arithmetic::{negate, ArithOp};
+use crate::comparison::{compare_values, fold_case, rfc_compare, CmpOp, SetOp};
+use crate::functions::{lookup_function, FilterFunction, FunctionType, FunctionValue};
+use crate::jsonpath::{
+    eval_path, eval_relative_path, parse_relative_query, parse_root_query, parse_string, skip_s,
+    Path, PathMode, QueryOptions,
+};
+use crate::pattern::{compile_iregexp, compile_with_flags};
+use itertools::Itertools;
+use regex::Regex;
 use serde_json::Value;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
 
 /// Represents a filter expression for JSONPath filtering.
 #[derive(Debug, Clone)]
 pub enum FilterExpr {
     Eq(Operand, Operand),                  // Equality comparison
     Ne(Operand, Operand),                  // Not equal comparison
+    IEq(Operand, Operand),                 // Equality with Unicode case folding of strings
     Lt(Operand, Operand),                  // Less than comparison
     Lte(Operand, Operand),                 // Less than or equal comparison
     Gt(Operand, Operand),                  // Greater than comparison
     Gte(Operand, Operand),                 // Greater than or equal comparison
+    In(Operand, Operand),                  // Membership: value in array
+    Nin(Operand, Operand),                 // Membership: value not in array
+    Contains(Operand, Operand),            // Array element or substring containment
+    SubsetOf(Operand, Operand),            // Every element in the other array
+    AnyOf(Operand, Operand),               // Arrays share an element
+    NoneOf(Operand, Operand),              // Arrays share no element
     And(Box<FilterExpr>, Box<FilterExpr>), // Logical AND
     Or(Box<FilterExpr>, Box<FilterExpr>),  // Logical OR
     Not(Box<FilterExpr>),                  // Logical NOT
     Truthy(Operand),                       // Truthiness check
+    Regex(Operand, RegexArg),              // `=~ /re/flags`, `match(a, re)` or `search(a, re)`
+}
+
+/// The pattern side of a regular-expression filter.
+#[derive(Debug, Clone)]
+pub enum RegexArg {
+    Compiled(Regex), // Literal pattern, compiled once while parsing
+    Dynamic { pattern: Operand, full: bool }, // Pattern read from the document for each node
 }
 
 /// Represents an operand in a filter expression.
 #[derive(Debug, Clone)]
 pub enum Operand {
-    CurrentPath(Vec<PathToken>), // Path reference, e.g. @.a['b'][0]
-    Literal(Value),              // Literal value, e.g. "abc", 123, true/false/null
-    Lower(Box<Operand>),         // Lowercase transformation
-    Upper(Box<Operand>),         // Uppercase transformation
-    Length(Box<Operand>),        // Length of array, object, or string
+    CurrentPath(Path),     // Path reference, e.g. @.a['b'][0], @.items[1:3], @..name
+    RootPath(Path),        // Absolute path reference, e.g. $.limits.max
+    Literal(Value),        // Literal value, e.g. "abc", 123, true/false/null
+    Call(FunctionCall),    // Registered function, e.g. length(@.name)
+    Test(Box<FilterExpr>), // Logical function argument, e.g. @.a == 1
+    Any(Box<Operand>),     // Some matched node must satisfy the comparison (the default)
+    All(Box<Operand>),     // Every matched node must satisfy the comparison
+    Arith(ArithOp, Box<Operand>, Box<Operand>), // Arithmetic, e.g. @.end - @.start
+    Neg(Box<Operand>),     // Unary minus
 }
 
-/// Represents a token in a JSONPath.
-#[derive(Debug, Clone)]
-pub enum PathToken {
-    Key(String), // Object key
-    Index(i64),  // Array index
-    Wildcard,    // Wildcard for any key or index
+/// A call to a registered filter function, with one argument per parameter.
+#[derive(Clone)]
+pub struct FunctionCall {
+    pub function: Arc<dyn FilterFunction>,
+    pub args: Vec<Operand>,
+}
+
+impl fmt::Debug for FunctionCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FunctionCall")
+            .field("function", &self.function.name())
+            .field("args", &self.args)
+            .finish()
+    }
 }
 
 use crate::jsonpath::ParseErr;
 use crate::parser::Parser;
 
 /// Parses a filter expression with logical OR (`||`) operators.
-pub fn parse_filter_or(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
-    let mut left = parse_filter_and(parser)?;
+pub fn parse_filter_or(parser: &mut Parser, mode: PathMode) -> Result<FilterExpr, ParseErr> {
+    let mut left = parse_filter_and(parser, mode)?;
     loop {
-        parser.skip_ws();
+        skip_s(parser, mode);
         if parser.peek_str("||") {
             parser.consume_char('|');
             parser.consume_char('|');
-            let right = parse_filter_and(parser)?;
+            let right = parse_filter_and(parser, mode)?;
             left = FilterExpr::Or(Box::new(left), Box::new(right));
         } else {
             break;
@@ -55,14 +94,14 @@ pub fn parse_filter_or(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
 }
 
 /// Parses a filter expression with logical AND (`&&`) operators.
-fn parse_filter_and(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
-    let mut left = parse_filter_not(parser)?;
+fn parse_filter_and(parser: &mut Parser, mode: PathMode) -> Result<FilterExpr, ParseErr> {
+    let mut left = parse_filter_not(parser, mode)?;
     loop {
-        parser.skip_ws();
+        skip_s(parser, mode);
         if parser.peek_str("&&") {
             parser.consume_char('&');
             parser.consume_char('&');
-            let right = parse_filter_not(parser)?;
+            let right = parse_filter_not(parser, mode)?;
             left = FilterExpr::And(Box::new(left), Box::new(right));
         } else {
             break;
@@ -72,27 +111,83 @@ fn parse_filter_and(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
 }
 
 /// Parses a filter expression with logical NOT (`!`) operator.
-fn parse_filter_not(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
-    parser.skip_ws();
+fn parse_filter_not(parser: &mut Parser, mode: PathMode) -> Result<FilterExpr, ParseErr> {
+    skip_s(parser, mode);
     if parser.consume_char('!') {
-        let inner = parse_filter_not(parser)?;
+        if mode == PathMode::Rfc9535 {
+            // RFC 9535 only negates a parenthesized expression or a test expression
+            skip_s(parser, mode);
+            if parser.consume_char('(') {
+                let inner = parse_filter_or(parser, mode)?;
+                skip_s(parser, mode);
+                parser.expect(')')?;
+                return Ok(FilterExpr::Not(Box::new(inner)));
+            }
+            if let Some(test) = parse_regex_function(parser, mode)? {
+                return Ok(FilterExpr::Not(Box::new(test)));
+            }
+            let operand = parse_operand(parser, mode)?;
+            ensure_testable(&operand)?;
+            return Ok(FilterExpr::Not(Box::new(FilterExpr::Truthy(operand))));
+        }
+        let inner = parse_filter_not(parser, mode)?;
         Ok(FilterExpr::Not(Box::new(inner)))
     } else {
-        parse_filter_compare(parser)
+        parse_filter_compare(parser, mode)
     }
 }
 
 /// Parses a filter expression with comparison operators.
-fn parse_filter_compare(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
-    parser.skip_ws();
+fn parse_filter_compare(parser: &mut Parser, mode: PathMode) -> Result<FilterExpr, ParseErr> {
+    skip_s(parser, mode);
     // Handle parenthesized expressions
+    let before = parser.pos();
     if parser.consume_char('(') {
-        let inner = parse_filter_or(parser)?;
+        let inner = parse_filter_or(parser, mode)?;
+        skip_s(parser, mode);
         parser.expect(')')?;
-        return Ok(inner);
+        // A group followed by an operator, as in `(@.a + 1) * 2 > 3`, is an arithmetic operand
+        skip_s(parser, mode);
+        if mode == PathMode::Rfc9535 || !continues_operand(parser) {
+            return Ok(inner);
+        }
+        parser.set_pos(before);
+    }
+    // match() / search() are tests, their result cannot be compared
+    if let Some(test) = parse_regex_function(parser, mode)? {
+        return Ok(test);
+    }
+    let left = parse_sum(parser, mode)?;
+    skip_s(parser, mode);
+    // Case-insensitive equality (not part of RFC 9535)
+    if mode == PathMode::Pragmatic && (parser.consume_str("=~=") || consume_keyword(parser, "ieq"))
+    {
+        skip_s(parser, mode);
+        let right = parse_sum(parser, mode)?;
+        return Ok(FilterExpr::IEq(left, right));
+    }
+    // Regex match against a `/pattern/flags` literal (not part of RFC 9535)
+    if mode == PathMode::Pragmatic && parser.consume_str("=~") {
+        skip_s(parser, mode);
+        let (pattern, flags) = parser.parse_regex_literal()?;
+        let re = compile_with_flags(&pattern, &flags)?;
+        return Ok(FilterExpr::Regex(left, RegexArg::Compiled(re)));
+    }
+    // Membership and containment operators (not part of RFC 9535)
+    if mode == PathMode::Pragmatic {
+        if let Some(op) = parse_set_op(parser) {
+            skip_s(parser, mode);
+            let right = parse_sum(parser, mode)?;
+            return Ok(match op {
+                SetOp::In => FilterExpr::In(left, right),
+                SetOp::Nin => FilterExpr::Nin(left, right),
+                SetOp::Contains => FilterExpr::Contains(left, right),
+                SetOp::SubsetOf => FilterExpr::SubsetOf(left, right),
+                SetOp::AnyOf => FilterExpr::AnyOf(left, right),
+                SetOp::NoneOf => FilterExpr::NoneOf(left, right),
+            });
+        }
     }
-    let left = parse_operand(parser)?;
-    parser.skip_ws();
     // Detect and parse comparison operators
     let op = if parser.peek_str("==") {
         parser.consume_char('=');
@@ -121,8 +216,12 @@ fn parse_filter_compare(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
     };
     // Build the appropriate filter expression
     if let Some(op) = op {
-        parser.skip_ws();
-        let right = parse_operand(parser)?;
+        skip_s(parser, mode);
+        let right = parse_sum(parser, mode)?;
+        if mode == PathMode::Rfc9535 {
+            ensure_comparable(&left)?;
+            ensure_comparable(&right)?;
+        }
         return Ok(match op {
             "==" => FilterExpr::Eq(left, right),
             "!=" => FilterExpr::Ne(left, right),
@@ -134,129 +233,324 @@ fn parse_filter_compare(parser: &mut Parser) -> Result<FilterExpr, ParseErr> {
         });
     }
     // If no comparison, treat as truthy check
+    if mode == PathMode::Rfc9535 {
+        ensure_testable(&left)?;
+    }
     Ok(FilterExpr::Truthy(left))
 }
 
+/// Returns true when the input continues with an arithmetic, comparison or membership
+/// operator, without consuming anything.
+fn continues_operand(parser: &mut Parser) -> bool {
+    let before = parser.pos();
+    let found = ["+", "-", "*", "/", "%", "<", ">", "==", "!=", "=~"]
+        .iter()
+        .any(|op| parser.peek_str(op))
+        || parse_set_op(parser).is_some()
+        || consume_keyword(parser, "ieq");
+    parser.set_pos(before);
+    found
+}
+
+/// Parses an operand with arithmetic (not part of RFC 9535): `+` and `-` bind looser
+/// than `*`, `/` and `%`, and all of them associate to the left.
+fn parse_sum(parser: &mut Parser, mode: PathMode) -> Result<Operand, ParseErr> {
+    if mode == PathMode::Rfc9535 {
+        return parse_operand(parser, mode);
+    }
+    let mut left = parse_product(parser, mode)?;
+    loop {
+        let before = parser.pos();
+        skip_s(parser, mode);
+        let op = if parser.consume_char('+') {
+            ArithOp::Add
+        } else if parser.consume_char('-') {
+            ArithOp::Sub
+        } else {
+            parser.set_pos(before);
+            break;
+        };
+        let right = parse_product(parser, mode)?;
+        left = Operand::Arith(op, Box::new(left), Box::new(right));
+    }
+    Ok(left)
+}
+
+/// Parses a product of unary operands: `a * b`, `a / b`, `a % b`.
+fn parse_product(parser: &mut Parser, mode: PathMode) -> Result<Operand, ParseErr> {
+    let mut left = parse_unary(parser, mode)?;
+    loop {
+        let before = parser.pos();
+        skip_s(parser, mode);
+        let op = if parser.consume_char('*') {
+            ArithOp::Mul
+        } else if parser.consume_char('/') {
+            ArithOp::Div
+        } else if parser.consume_char('%') {
+            ArithOp::Rem
+        } else {
+            parser.set_pos(before);
+            break;
+        };
+        let right = parse_unary(parser, mode)?;
+        left = Operand::Arith(op, Box::new(left), Box::new(right));
+    }
+    Ok(left)
+}
+
+/// Parses a unary minus, a parenthesized arithmetic operand, or a plain operand.
+fn parse_unary(parser: &mut Parser, mode: PathMode) -> Result<Operand, ParseErr> {
+    skip_s(parser, mode);
+    let before = parser.pos();
+    if parser.consume_char('-') {
+        // A minus sign directly followed by a digit is part of a number literal
+        if !parser.peek_char().is_some_and(|c| c.is_ascii_digit()) {
+            let inner = parse_unary(parser, mode)?;
+            return Ok(Operand::Neg(Box::new(inner)));
+        }
+        parser.set_pos(before);
+    }
+    if parser.consume_char('(') {
+        let inner = parse_sum(parser, mode)?;
+        skip_s(parser, mode);
+        parser.expect(')')?;
+        return Ok(inner);
+    }
+    parse_operand(parser, mode)
+}
+
+/// Parses a call to a registered filter function, e.g. `length(@.name)`, checking the
+/// arguments against the function's parameter types. Returns `None` when the input does
+/// not start with a name directly followed by `(`.
+fn parse_function_call(parser: &mut Parser, mode: PathMode) -> Result<Option<Operand>, ParseErr> {
+    let before = parser.pos();
+    if !parser.peek_char().is_some_and(|c| c.is_ascii_lowercase()) {
+        return Ok(None);
+    }
+    let name = parser.parse_identifier()?;
+    if !parser.consume_char('(') {
+        parser.set_pos(before);
+        return Ok(None);
+    }
+    let function = lookup_function(&name)
+        .filter(|f| f.available_in(mode))
+        .ok_or_else(|| ParseErr::InvalidSyntax(format!("unknown function `{name}`")))?;
+    let mut args = Vec::new();
+    for (i, ty) in function.params().iter().enumerate() {
+        if i > 0 {
+            skip_s(parser, mode);
+            // Optional parameters may be left out
+            if i >= function.required_params() && parser.peek_char() == Some(')') {
+                break;
+            }
+            parser.expect(',')?;
+        }
+        let arg = match ty {
+            FunctionType::Logical => Operand::Test(Box::new(parse_filter_or(parser, mode)?)),
+            _ => parse_sum(parser, mode)?,
+        };
+        check_argument(&arg, *ty, mode)?;
+        args.push(arg);
+    }
+    skip_s(parser, mode);
+    if !parser.consume_char(')') {
+        return Err(ParseErr::InvalidSyntax(format!(
+            "function `{name}` expects at most {} argument(s)",
+            function.params().len()
+        )));
+    }
+    Ok(Some(Operand::Call(FunctionCall { function, args })))
+}
+
+/// Checks that a function argument fits the declared parameter type
+/// (RFC 9535 section 2.4.3). Logical arguments are always parsed as logical expressions.
+fn check_argument(arg: &Operand, ty: FunctionType, mode: PathMode) -> Result<(), ParseErr> {
+    match (ty, arg) {
+        (FunctionType::Logical, _) => Ok(()),
+        (FunctionType::Nodes, Operand::CurrentPath(_) | Operand::RootPath(_)) => Ok(()),
+        (FunctionType::Nodes, Operand::Call(c)) if c.function.result() == FunctionType::Nodes => {
+            Ok(())
+        }
+        (FunctionType::Nodes, _) => Err(ParseErr::InvalidSyntax("argument must be a query".into())),
+        (FunctionType::Value, _) if mode == PathMode::Rfc9535 => ensure_comparable(arg),
+        (FunctionType::Value, _) => Ok(()),
+    }
+}
+
+/// Consumes a membership or containment keyword, which must not run into a following name.
+fn parse_set_op(parser: &mut Parser) -> Option<SetOp> {
+    const KEYWORDS: [(&str, SetOp); 6] = [
+        ("nin", SetOp::Nin),
+        ("in", SetOp::In),
+        ("contains", SetOp::Contains),
+        ("subsetof", SetOp::SubsetOf),
+        ("anyof", SetOp::AnyOf),
+        ("noneof", SetOp::NoneOf),
+    ];
+    KEYWORDS
+        .into_iter()
+        .find(|(keyword, _)| consume_keyword(parser, keyword))
+        .map(|(_, op)| op)
+}
+
+/// Consumes `keyword` unless it runs into a following name (`in` but not `index`).
+fn consume_keyword(parser: &mut Parser, keyword: &str) -> bool {
+    let before = parser.pos();
+    if parser.consume_str(keyword) {
+        if !parser
+            .peek_char()
+            .is_some_and(|c| c == '_' || c.is_alphanumeric())
+        {
+            return true;
+        }
+        parser.set_pos(before);
+    }
+    false
+}
+
+/// Parses `match(value, pattern)` (whole-string match) or `search(value, pattern)`
+/// (substring match). Returns `None` when the input starts with neither function.
+/// A literal pattern is compiled here; any other pattern is compiled when evaluated.
+fn parse_regex_function(
+    parser: &mut Parser,
+    mode: PathMode,
+) -> Result<Option<FilterExpr>, ParseErr> {
+    let full = if parser.consume_str("match(") {
+        true
+    } else if parser.consume_str("search(") {
+        false
+    } else {
+        return Ok(None);
+    };
+    let target = parse_sum(parser, mode)?;
+    skip_s(parser, mode);
+    parser.expect(',')?;
+    let pattern = parse_sum(parser, mode)?;
+    skip_s(parser, mode);
+    parser.expect(')')?;
+    if mode == PathMode::Rfc9535 {
+        ensure_comparable(&target)?;
+        ensure_comparable(&pattern)?;
+    }
+    let arg = match pattern {
+        Operand::Literal(Value::String(p)) => RegexArg::Compiled(compile_iregexp(&p, full)?),
+        pattern => RegexArg::Dynamic { pattern, full },
+    };
+    Ok(Some(FilterExpr::Regex(target, arg)))
+}
+
+/// RFC 9535: comparison operands must be literals, singular queries, or functions
+/// returning a value.
+fn ensure_comparable(op: &Operand) -> Result<(), ParseErr> {
+    match op {
+        Operand::CurrentPath(path) | Operand::RootPath(path) if !path.is_singular() => Err(
+            ParseErr::InvalidSyntax("non-singular query is not comparable".into()),
+        ),
+        Operand::Call(c) if c.function.result() != FunctionType::Value => Err(
+            ParseErr::InvalidSyntax("function result is not comparable".into()),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// RFC 9535: a test expression must be a filter query (an existence test); literals and
+/// functions returning a plain value cannot stand alone.
+fn ensure_testable(op: &Operand) -> Result<(), ParseErr> {
+    match op {
+        Operand::CurrentPath(_) | Operand::RootPath(_) => Ok(()),
+        Operand::Call(c) if c.function.result() != FunctionType::Value => Ok(()),
+        Operand::Literal(_) => Err(ParseErr::InvalidSyntax(
+            "literal is not a test expression".into(),
+        )),
+        _ => Err(ParseErr::InvalidSyntax(
+            "function result is not a test expression".into(),
+        )),
+    }
+}
+
 /// Parses an operand for filter expressions.
-fn parse_operand(parser: &mut Parser) -> Result<Operand, ParseErr> {
-    parser.skip_ws();
+fn parse_operand(parser: &mut Parser, mode: PathMode) -> Result<Operand, ParseErr> {
+    skip_s(parser, mode);
     // Parse string literal
     if parser.peek_char() == Some('"') || parser.peek_char() == Some('\'') {
-        return Ok(Operand::Literal(Value::String(
-            parser.parse_quoted_string()?,
-        )));
+        return Ok(Operand::Literal(Value::String(parse_string(parser, mode)?)));
+    }
+    // Parse any() / all() quantifiers over a node list (not part of RFC 9535)
+    if mode == PathMode::Pragmatic && parser.consume_str("any(") {
+        let inner = parse_sum(parser, mode)?;
+        skip_s(parser, mode);
+        parser.expect(')')?;
+        return Ok(Operand::Any(Box::new(inner)));
+    }
+    if mode == PathMode::Pragmatic && parser.consume_str("all(") {
+        let inner = parse_sum(parser, mode)?;
+        skip_s(parser, mode);
+        parser.expect(')')?;
+        return Ok(Operand::All(Box::new(inner)));
+    }
+    // Parse a call to a registered function
+    if let Some(call) = parse_function_call(parser, mode)? {
+        return Ok(call);
     }
     // Parse boolean literals
-    if parser.peek_str("true") {
-        for _ in 0..4 {
-            parser.consume_char('t');
-            parser.consume_char('r');
-            parser.consume_char('u');
-            parser.consume_char('e');
-        }
+    if parser.consume_str("true") {
         return Ok(Operand::Literal(Value::Bool(true)));
     }
-    if parser.peek_str("false") {
-        for _ in 0..5 {
-            parser.consume_char('f');
-            parser.consume_char('a');
-            parser.consume_char('l');
-            parser.consume_char('s');
-            parser.consume_char('e');
-        }
+    if parser.consume_str("false") {
         return Ok(Operand::Literal(Value::Bool(false)));
     }
     // Parse null literal
-    if parser.peek_str("null") {
-        for _ in 0..4 {
-            parser.consume_char('n');
-            parser.consume_char('u');
-            parser.consume_char('l');
-            parser.consume_char('l');
-        }
+    if parser.consume_str("null") {
         return Ok(Operand::Literal(Value::Null));
     }
-    // Parse lower() transformation
-    if parser.peek_str("lower(") {
-        for _ in 0..6 {
-            parser.consume_char('l');
-            parser.consume_char('o');
-            parser.consume_char('w');
-            parser.consume_char('e');
-            parser.consume_char('r');
-            parser.consume_char('(');
-        }
-        let inner = parse_operand(parser)?;
-        parser.expect(')')?;
-        return Ok(Operand::Lower(Box::new(inner)));
-    }
-    // Parse upper() transformation
-    if parser.peek_str("upper(") {
-        for _ in 0..6 {
-            parser.consume_char('u');
-            parser.consume_char('p');
-            parser.consume_char('p');
-            parser.consume_char('e');
-            parser.consume_char('r');
-            parser.consume_char('(');
-        }
-        let inner = parse_operand(parser)?;
-        parser.expect(')')?;
-        return Ok(Operand::Upper(Box::new(inner)));
-    }
-    // Parse length() transformation
-    if parser.peek_str("length(") {
-        for _ in 0..7 {
-            parser.consume_char('l');
-            parser.consume_char('e');
-            parser.consume_char('n');
-            parser.consume_char('g');
-            parser.consume_char('t');
-            parser.consume_char('h');
-            parser.consume_char('(');
+    // Parse array literal, e.g. ['prod', 'staging'] (not part of RFC 9535)
+    if mode == PathMode::Pragmatic && parser.consume_char('[') {
+        let mut items = Vec::new();
+        skip_s(parser, mode);
+        if !parser.consume_char(']') {
+            loop {
+                items.push(parse_nested_literal(parser, mode)?);
+                skip_s(parser, mode);
+                if parser.consume_char(']') {
+                    break;
+                }
+                parser.expect(',')?;
+            }
         }
-        let inner = parse_operand(parser)?;
-        parser.expect(')')?;
-        return Ok(Operand::Length(Box::new(inner)));
+        return Ok(Operand::Literal(Value::Array(items)));
     }
-    // Parse path reference starting with '@'
-    if parser.peek_char() == Some('@') {
-        parser.consume_char('@');
-        let mut tokens = Vec::new();
-        loop {
-            parser.skip_ws();
-            // Parse object key
-            if parser.consume_char('.') {
-                if parser.consume_char('*') {
-                    tokens.push(PathToken::Wildcard);
-                    continue;
-                }
-                let k = parser.parse_identifier()?;
-                tokens.push(PathToken::Key(k));
-                continue;
-            // Parse array index or key in brackets
-            } else if parser.consume_char('[') {
-                if parser.consume_char('*') {
-                    parser.expect(']')?;
-                    tokens.push(PathToken::Wildcard);
-                    continue;
+    // Parse object literal, e.g. {"a": 1, 'b': [2]} (not part of RFC 9535)
+    if mode == PathMode::Pragmatic && parser.consume_char('{') {
+        let mut members = serde_json::Map::new();
+        skip_s(parser, mode);
+        if !parser.consume_char('}') {
+            loop {
+                skip_s(parser, mode);
+                if !matches!(parser.peek_char(), Some('"' | '\'')) {
+                    return Err(ParseErr::InvalidSyntax(
+                        "object literal keys must be strings".into(),
+                    ));
                 }
-                if parser.peek_char() == Some('"') || parser.peek_char() == Some('\'') {
-                    let k = parser.parse_quoted_string()?;
-                    parser.expect(']')?;
-                    tokens.push(PathToken::Key(k));
-                    continue;
+                let key = parse_string(parser, mode)?;
+                skip_s(parser, mode);
+                parser.expect(':')?;
+                members.insert(key, parse_nested_literal(parser, mode)?);
+                skip_s(parser, mode);
+                if parser.consume_char('}') {
+                    break;
                 }
-                let idx_content = parser.capture_until(']')?;
-                parser.expect(']')?;
-                let mut tmp = Parser::new(idx_content);
-                let idx = tmp.parse_int()?;
-                tokens.push(PathToken::Index(idx));
-                continue;
+                parser.expect(',')?;
             }
-            break;
         }
-        return Ok(Operand::CurrentPath(tokens));
+        return Ok(Operand::Literal(Value::Object(members)));
+    }
+    // Parse path reference starting with '@'
+    if parser.peek_char() == Some('@') {
+        return Ok(Operand::CurrentPath(parse_relative_query(parser, mode)?));
+    }
+    // Parse absolute path reference starting with '$'
+    if parser.peek_char() == Some('$') {
+        return Ok(Operand::RootPath(parse_root_query(parser, mode)?));
     }
     // Parse number literal
     if parser
@@ -264,56 +558,189 @@ fn parse_operand(parser: &mut Parser) -> Result<Operand, ParseErr> {
         .map(|c| c == '-' || c.is_ascii_digit())
         .unwrap_or(false)
     {
-        let n = parser.parse_number_literal()?;
+        let n = match mode {
+            PathMode::Pragmatic => parser.parse_number_literal()?,
+            PathMode::Rfc9535 => parser.parse_json_number()?,
+        };
         return Ok(Operand::Literal(n));
     }
     // If none matched, return syntax error
     Err(ParseErr::InvalidSyntax("invalid operand".into()))
 }
 
+/// Parses an element of an array or object literal, which must be a literal itself.
+fn parse_nested_literal(parser: &mut Parser, mode: PathMode) -> Result<Value, ParseErr> {
+    match parse_operand(parser, mode)? {
+        Operand::Literal(v) => Ok(v),
+        _ => Err(ParseErr::InvalidSyntax(
+            "array and object literal elements must be literals".into(),
+        )),
+    }
+}
+
 /// Evaluates a filter expression against a JSON value.
-pub fn eval_filter(expr: &FilterExpr, current: &Value) -> bool {
+/// `root` is the document root that `$` paths inside the filter refer to.
+pub fn eval_filter(expr: &FilterExpr, current: &Value, root: &Value, opts: QueryOptions) -> bool {
     match expr {
-        // Comparison operators use cmp_values for evaluation
-        FilterExpr::Eq(a, b) => {
-            cmp_values(&eval_operand(a, current), &eval_operand(b, current), |o| {
-                o == 0
-            })
+        // Comparison operators
+        FilterExpr::Eq(a, b) => compare(CmpOp::Eq, a, b, current, root, opts),
+        FilterExpr::Ne(a, b) => compare(CmpOp::Ne, a, b, current, root, opts),
+        FilterExpr::IEq(a, b) => {
+            let opts = QueryOptions {
+                ignore_case: true,
+                ..opts
+            };
+            compare(CmpOp::Eq, a, b, current, root, opts)
         }
-        FilterExpr::Ne(a, b) => {
-            cmp_values(&eval_operand(a, current), &eval_operand(b, current), |o| {
-                o != 0
-            })
+        FilterExpr::Lt(a, b) => compare(CmpOp::Lt, a, b, current, root, opts),
+        FilterExpr::Lte(a, b) => compare(CmpOp::Lte, a, b, current, root, opts),
+        FilterExpr::Gt(a, b) => compare(CmpOp::Gt, a, b, current, root, opts),
+        FilterExpr::Gte(a, b) => compare(CmpOp::Gte, a, b, current, root, opts),
+        // Membership and containment operators
+        FilterExpr::In(a, b) => set_compare(SetOp::In, a, b, current, root, opts),
+        FilterExpr::Nin(a, b) => set_compare(SetOp::Nin, a, b, current, root, opts),
+        FilterExpr::Contains(a, b) => set_compare(SetOp::Contains, a, b, current, root, opts),
+        FilterExpr::SubsetOf(a, b) => set_compare(SetOp::SubsetOf, a, b, current, root, opts),
+        FilterExpr::AnyOf(a, b) => set_compare(SetOp::AnyOf, a, b, current, root, opts),
+        FilterExpr::NoneOf(a, b) => set_compare(SetOp::NoneOf, a, b, current, root, opts),
+        // Logical operators
+        FilterExpr::And(l, r) => {
+            eval_filter(l, current, root, opts) && eval_filter(r, current, root, opts)
         }
-        FilterExpr::Lt(a, b) => {
-            cmp_values(&eval_operand(a, current), &eval_operand(b, current), |o| {
-                o < 0
-            })
+        FilterExpr::Or(l, r) => {
+            eval_filter(l, current, root, opts) || eval_filter(r, current, root, opts)
         }
-        FilterExpr::Lte(a, b) => {
-            cmp_values(&eval_operand(a, current), &eval_operand(b, current), |o| {
-                o <= 0
-            })
+        FilterExpr::Not(i) => !eval_filter(i, current, root, opts),
+        // Regex match: some string value of the target matches the pattern
+        FilterExpr::Regex(target, arg) => {
+            let re = match arg {
+                RegexArg::Compiled(re) => Cow::Borrowed(re),
+                RegexArg::Dynamic { pattern, full } => {
+                    match eval_operand(pattern, current, root, opts).first() {
+                        Some(Value::String(p)) => match compile_iregexp(p, *full) {
+                            Ok(re) => Cow::Owned(re),
+                            Err(_) => return false,
+                        },
+                        _ => return false,
+                    }
+                }
+            };
+            eval_operand(target, current, root, opts)
+                .iter()
+                .any(|v| v.as_str().is_some_and(|s| re.is_match(s)))
         }
-        FilterExpr::Gt(a, b) => {
-            cmp_values(&eval_operand(a, current), &eval_operand(b, current), |o| {
-                o > 0
-            })
+        // A bare query is an existence test in RFC 9535; the pragmatic dialect keeps testing
+        // the truthiness of a singular path and uses existence for multi-node paths
+        FilterExpr::Truthy(op) => {
+            let values = eval_operand(op, current, root, opts);
+            match op {
+                Operand::CurrentPath(path) | Operand::RootPath(path)
+                    if opts.mode == PathMode::Rfc9535 || !path.is_singular() =>
+                {
+                    !values.is_empty()
+                }
+                Operand::Call(c) if c.function.result() == FunctionType::Nodes => {
+                    !values.is_empty()
+                }
+                Operand::All(_) => !values.is_empty() && values.iter().all(truthy),
+                _ => values.iter().any(truthy),
+            }
         }
-        FilterExpr::Gte(a, b) => {
-            cmp_values(&eval_operand(a, current), &eval_operand(b, current), |o| {
-                o >= 0
-            })
+    }
+}
+
+/// How the nodes of a multi-node operand take part in a comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quantifier {
+    Any,
+    All,
+}
+
+impl Quantifier {
+    /// `all(...)` selects `All`; every other operand compares as `Any`.
+    fn of(op: &Operand) -> Self {
+        match op {
+            Operand::All(_) => Quantifier::All,
+            _ => Quantifier::Any,
+        }
+    }
+
+    /// Applies the predicate over the values. An empty list is Nothing (`None`) under `Any`
+    /// and never holds under `All`.
+    fn holds(self, values: &[Value], mut pred: impl FnMut(Option<&Value>) -> bool) -> bool {
+        match self {
+            Quantifier::Any if values.is_empty() => pred(None),
+            Quantifier::Any => values.iter().any(|v| pred(Some(v))),
+            Quantifier::All => !values.is_empty() && values.iter().all(|v| pred(Some(v))),
         }
-        // Logical operators
-        FilterExpr::And(l, r) => eval_filter(l, current) && eval_filter(r, current),
-        FilterExpr::Or(l, r) => eval_filter(l, current) || eval_filter(r, current),
-        FilterExpr::Not(i) => !eval_filter(i, current),
-        // Truthiness check
-        FilterExpr::Truthy(op) => truthy(&eval_operand(op, current)),
     }
 }
 
+/// Compares two operands: the pragmatic dialect uses the query's comparison mode with
+/// multi-node operands per their quantifier, RFC 9535 uses its own comparison rules on
+/// singular operands. In both, a missing value is Nothing rather than `null`: Nothing only
+/// equals Nothing and is not ordered against anything.
+fn compare(
+    op: CmpOp,
+    a: &Operand,
+    b: &Operand,
+    current: &Value,
+    root: &Value,
+    opts: QueryOptions,
+) -> bool {
+    let (left, right) = eval_operands(a, b, current, root, opts);
+    match opts.mode {
+        PathMode::Pragmatic => Quantifier::of(a).holds(&left, |x| {
+            Quantifier::of(b).holds(&right, |y| match (x, y) {
+                (Some(x), Some(y)) => compare_values(op, x, y, opts.comparison),
+                _ => rfc_compare(op, x, y),
+            })
+        }),
+        PathMode::Rfc9535 => rfc_compare(op, left.first(), right.first()),
+    }
+}
+
+/// Applies a membership or containment operator, with multi-node operands handled per
+/// their quantifier as in `compare`. Nothing is not an element of any array (so `nin`
+/// holds for it) and contains nothing.
+fn set_compare(
+    op: SetOp,
+    a: &Operand,
+    b: &Operand,
+    current: &Value,
+    root: &Value,
+    opts: QueryOptions,
+) -> bool {
+    let (left, right) = eval_operands(a, b, current, root, opts);
+    Quantifier::of(a).holds(&left, |x| {
+        Quantifier::of(b).holds(&right, |y| match (x, y) {
+            (Some(x), Some(y)) => op.holds(x, y, opts.comparison),
+            (None, Some(Value::Array(_))) => op == SetOp::Nin,
+            _ => false,
+        })
+    })
+}
+
+/// Evaluates both sides of a comparison, folding the case of strings when the query
+/// ignores case.
+fn eval_operands(
+    a: &Operand,
+    b: &Operand,
+    current: &Value,
+    root: &Value,
+    opts: QueryOptions,
+) -> (Vec<Value>, Vec<Value>) {
+    let left = eval_operand(a, current, root, opts);
+    let right = eval_operand(b, current, root, opts);
+    if !opts.ignore_case {
+        return (left, right);
+    }
+    (
+        left.into_iter().map(fold_case).collect(),
+        right.into_iter().map(fold_case).collect(),
+    )
+}
+
 /// Determines the truthiness of a JSON value.
 fn truthy(v: &Value) -> bool {
     match v {
@@ -327,79 +754,80 @@ fn truthy(v: &Value) -> bool {
 }
 
 /// Evaluates an operand against the current JSON value.
-fn eval_operand(op: &Operand, current: &Value) -> Value {
+/// Paths yield every matched node (empty when nothing matches) and functions apply to
+/// each value of their argument.
+fn eval_operand(op: &Operand, current: &Value, root: &Value, opts: QueryOptions) -> Vec<Value> {
     match op {
-        Operand::Literal(v) => v.clone(),
-        // Lowercase transformation
-        Operand::Lower(inner) => {
-            let v = eval_operand(inner, current);
-            if let Some(s) = v.as_str() {
-                Value::String(s.to_lowercase())
-            } else {
-                v
-            }
-        }
-        // Uppercase transformation
-        Operand::Upper(inner) => {
-            let v = eval_operand(inner, current);
-            if let Some(s) = v.as_str() {
-                Value::String(s.to_uppercase())
-            } else {
-                v
-            }
-        }
-        // Length calculation
-        Operand::Length(inner) => {
-            let v = eval_operand(inner, current);
-            let len = match v {
-                Value::Array(a) => a.len() as i64,
-                Value::Object(m) => m.len() as i64,
-                Value::String(s) => s.chars().count() as i64,
-                _ => 0,
-            };
-            Value::from(len)
-        }
-        // Path evaluation
-        Operand::CurrentPath(tokens) => {
-            let mut nodes = vec![current];
-            for t in tokens {
-                nodes = match t {
-                    // Object key lookup
-                    PathToken::Key(k) => nodes
-                        .into_iter()
-                        .flat_map(|n| match n {
-                            Value::Object(m) => m.get(k).into_iter().collect(),
-                            _ => Vec::new(),
-                        })
-                        .collect(),
-                    // Array index lookup
-                    PathToken::Index(i) => {
-                        if *i < 0 {
-                            Vec::new()
-                        } else {
-                            let idx = *i as usize;
-                            nodes
-                                .into_iter()
-                                .flat_map(|n| match n {
-                                    Value::Array(a) => a.get(idx).into_iter().collect(),
-                                    _ => Vec::new(),
-                                })
-                                .collect()
-                        }
-                    }
-                    // Wildcard: all values in array or object
-                    PathToken::Wildcard => nodes
-                        .into_iter()
-                        .flat_map(|n| match n {
-                            Value::Array(a) => a.iter().collect(),
-                            Value::Object(m) => m.values().collect(),
-                            _ => Vec::new(),
-                        })
-                        .collect(),
-                }
-            }
-            // Return first matched node or Null
-            nodes.first().cloned().cloned().unwrap_or(Value::Null)
+        Operand::Literal(v) => vec![v.clone()],
+        // Function call, see `call_function`
+        Operand::Call(call) => call_function(call, current, root, opts),
+        Operand::Test(expr) => vec![Value::Bool(eval_filter(expr, current, root, opts))],
+        // Path evaluation: every matched node
+        Operand::CurrentPath(path) => eval_relative_path(current, root, path, opts)
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect(),
+        Operand::RootPath(path) => eval_path(root, path, opts)
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect(),
+        // Arithmetic over every pair of values; non-numbers yield nothing
+        Operand::Arith(op, a, b) => {
+            let right = eval_operand(b, current, root, opts);
+            eval_operand(a, current, root, opts)
+                .iter()
+                .flat_map(|x| right.iter().filter_map(move |y| op.apply(x, y)))
+                .collect()
         }
+        Operand::Neg(inner) => eval_operand(inner, current, root, opts)
+            .iter()
+            .filter_map(negate)
+            .collect(),
+        // Quantifiers only change how the values are compared
+        Operand::Any(inner) | Operand::All(inner) => eval_operand(inner, current, root, opts),
     }
 }
+
+/// Evaluates a function call. A value parameter given several values (a multi-node path
+/// in the pragmatic dialect) calls the function once per value, and the results are
+/// collected; Nothing results are dropped.
+fn call_function(
+    call: &FunctionCall,
+    current: &Value,
+    root: &Value,
+    opts: QueryOptions,
+) -> Vec<Value> {
+    let choices: Vec<Vec<FunctionValue>> = call
+        .function
+        .params()
+        .iter()
+        .zip(&call.args)
+        .map(|(ty, arg)| {
+            let values = eval_operand(arg, current, root, opts);
+            match ty {
+                FunctionType::Logical => vec![FunctionValue::Logical(
+                    values.first() == Some(&Value::Bool(true)),
+                )],
+                FunctionType::Nodes => vec![FunctionValue::Nodes(values)],
+                FunctionType::Value if values.is_empty() => vec![FunctionValue::Value(None)],
+                FunctionType::Value => values
+                    .into_iter()
+                    .map(|v| FunctionValue::Value(Some(v)))
+                    .collect(),
+            }
+        })
+        .collect();
+    let calls: Vec<Vec<FunctionValue>> = if choices.is_empty() {
+        vec![Vec::new()]
+    } else {
+        choices.into_iter().multi_cartesian_product().collect()
+    };
+    calls
+        .iter()
+        .flat_map(|args| match call.function.call(args, opts) {
+            FunctionValue::Value(v) => v.into_iter().collect(),
+            FunctionValue::Logical(b) => vec![Value::Bool(b)],
+            FunctionValue::Nodes(values) => values,
+        })
+        .collect()
+}
@@ -0,0 +1,516 @@
+use crate::comparison::{fold_case, SetOp};
+use crate::jsonpath::{PathMode, QueryOptions};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Types of filter function parameters and results, as in RFC 9535 section 2.4.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionType {
+    /// A single JSON value, or Nothing. Arguments are literals, singular queries or
+    /// functions returning a value.
+    Value,
+    /// A boolean. Arguments are test or comparison expressions.
+    Logical,
+    /// A node list. Arguments are queries or functions returning nodes.
+    Nodes,
+}
+
+/// An evaluated function argument or result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FunctionValue {
+    /// A JSON value; `None` stands for Nothing (e.g. a query that matched no node).
+    Value(Option<Value>),
+    /// A boolean.
+    Logical(bool),
+    /// The values of the nodes of a node list.
+    Nodes(Vec<Value>),
+}
+
+/// A function that can be called in filter expressions, e.g. `[?(length(@.name) > 3)]`.
+///
+/// Arguments are checked against `params` when a filter is parsed, so `call` receives
+/// one `FunctionValue` of the declared type per given parameter. Register
+/// implementations with `register_function`.
+pub trait FilterFunction: Send + Sync {
+    /// The name used in filter expressions.
+    fn name(&self) -> &str;
+
+    /// The parameter types, in order.
+    fn params(&self) -> &[FunctionType];
+
+    /// How many leading parameters are required; the rest are optional and are left out
+    /// of `call` when not given (all parameters are required by default).
+    fn required_params(&self) -> usize {
+        self.params().len()
+    }
+
+    /// The result type.
+    fn result(&self) -> FunctionType;
+
+    /// Whether the function can be used in the given dialect (all dialects by default).
+    fn available_in(&self, _mode: PathMode) -> bool {
+        true
+    }
+
+    /// Calls the function with arguments matching `params`, without any optional
+    /// parameters that were not given. `opts` are the options of the query being evaluated
+    /// (dialect, comparison mode, case sensitivity).
+    fn call(&self, args: &[FunctionValue], opts: QueryOptions) -> FunctionValue;
+}
+
+/// Registered functions by name.
+type Registry = RwLock<HashMap<String, Arc<dyn FilterFunction>>>;
+
+/// The global function registry, created with the built-in functions.
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let builtins: Vec<Arc<dyn FilterFunction>> = vec![
+            Arc::new(Length),
+            Arc::new(Count),
+            Arc::new(ValueOf),
+            Arc::new(StringMap {
+                name: "lower",
+                map: str::to_lowercase,
+            }),
+            Arc::new(StringMap {
+                name: "upper",
+                map: str::to_uppercase,
+            }),
+            Arc::new(StringMap {
+                name: "trim",
+                map: |s| s.trim().to_string(),
+            }),
+            Arc::new(StringTest {
+                name: "starts_with",
+                test: |s, prefix| s.starts_with(prefix),
+            }),
+            Arc::new(StringTest {
+                name: "ends_with",
+                test: |s, suffix| s.ends_with(suffix),
+            }),
+            Arc::new(Contains),
+            Arc::new(Substring),
+            Arc::new(Exists),
+            Arc::new(TypeOf),
+            Arc::new(TypeTest {
+                name: "is_null",
+                test: Value::is_null,
+            }),
+            Arc::new(TypeTest {
+                name: "is_boolean",
+                test: Value::is_boolean,
+            }),
+            Arc::new(TypeTest {
+                name: "is_number",
+                test: Value::is_number,
+            }),
+            Arc::new(TypeTest {
+                name: "is_string",
+                test: Value::is_string,
+            }),
+            Arc::new(TypeTest {
+                name: "is_array",
+                test: Value::is_array,
+            }),
+            Arc::new(TypeTest {
+                name: "is_object",
+                test: Value::is_object,
+            }),
+        ];
+        let map = builtins
+            .into_iter()
+            .map(|f| (f.name().to_string(), f))
+            .collect();
+        RwLock::new(map)
+    })
+}
+
+/// Names the filter parser handles itself (`match`/`search` comparisons and the `any`/`all`
+/// quantifiers); a function registered under one of them could never be called.
+const RESERVED_NAMES: [&str; 4] = ["match", "search", "any", "all"];
+
+/// The RFC 9535 standard functions, which cannot be replaced.
+const STANDARD_NAMES: [&str; 3] = ["length", "count", "value"];
+
+/// Registers a filter function, replacing any function of the same name.
+/// The function is available to every filter parsed afterwards.
+///
+/// Fails without registering anything when the name cannot be called from a filter (it
+/// must be a lowercase ASCII letter followed by ASCII letters, digits or `_`), is reserved by
+/// the filter syntax (`match`, `search`, `any`, `all`) or belongs to an RFC 9535 standard
+/// function (`length`, `count`, `value`).
+pub fn register_function(function: impl FilterFunction + 'static) -> Result<(), String> {
+    let name = function.name();
+    if !is_function_name(name) {
+        return Err(format!("'{name}' is not a valid function name"));
+    }
+    if RESERVED_NAMES.contains(&name) {
+        return Err(format!("function name '{name}' is reserved"));
+    }
+    if STANDARD_NAMES.contains(&name) {
+        return Err(format!("cannot replace standard function '{name}'"));
+    }
+    let mut map = registry().write().unwrap_or_else(|e| e.into_inner());
+    map.insert(name.to_string(), Arc::new(function));
+    Ok(())
+}
+
+/// Whether `name` has the shape of a function name in a filter, as accepted by the parser.
+fn is_function_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Looks up a registered function by name.
+pub(crate) fn lookup_function(name: &str) -> Option<Arc<dyn FilterFunction>> {
+    let map = registry().read().unwrap_or_else(|e| e.into_inner());
+    map.get(name).cloned()
+}
+
+/// `length(value)`: number of characters of a string, elements of an array or members of
+/// an object. Other values have no length: Nothing in RFC 9535, 0 in the pragmatic dialect.
+struct Length;
+
+impl FilterFunction for Length {
+    fn name(&self) -> &str {
+        "length"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn call(&self, args: &[FunctionValue], opts: QueryOptions) -> FunctionValue {
+        let len = match &args[0] {
+            FunctionValue::Value(Some(Value::Array(a))) => a.len(),
+            FunctionValue::Value(Some(Value::Object(m))) => m.len(),
+            FunctionValue::Value(Some(Value::String(s))) => s.chars().count(),
+            _ if opts.mode == PathMode::Rfc9535 => return FunctionValue::Value(None),
+            _ => 0,
+        };
+        FunctionValue::Value(Some(Value::from(len)))
+    }
+}
+
+/// `count(nodes)`: number of nodes in a node list.
+struct Count;
+
+impl FilterFunction for Count {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Nodes]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        match &args[0] {
+            FunctionValue::Nodes(nodes) => FunctionValue::Value(Some(Value::from(nodes.len()))),
+            _ => FunctionValue::Value(None),
+        }
+    }
+}
+
+/// `value(nodes)`: the value of the only node of a node list; Nothing when the list is
+/// empty or has several nodes.
+struct ValueOf;
+
+impl FilterFunction for ValueOf {
+    fn name(&self) -> &str {
+        "value"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Nodes]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        match &args[0] {
+            FunctionValue::Nodes(nodes) if nodes.len() == 1 => {
+                FunctionValue::Value(Some(nodes[0].clone()))
+            }
+            _ => FunctionValue::Value(None),
+        }
+    }
+}
+
+/// String functions that map a string to a string: `lower(value)`, `upper(value)` and
+/// `trim(value)`. Other values are returned unchanged. Not part of RFC 9535.
+struct StringMap {
+    name: &'static str,
+    map: fn(&str) -> String,
+}
+
+impl FilterFunction for StringMap {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn available_in(&self, mode: PathMode) -> bool {
+        mode == PathMode::Pragmatic
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        match &args[0] {
+            FunctionValue::Value(Some(Value::String(s))) => {
+                FunctionValue::Value(Some(Value::String((self.map)(s))))
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// String predicates over two strings: `starts_with(value, prefix)` and
+/// `ends_with(value, suffix)`. False unless both arguments are strings. Not part of RFC 9535.
+struct StringTest {
+    name: &'static str,
+    test: fn(&str, &str) -> bool,
+}
+
+impl FilterFunction for StringTest {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value, FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Logical
+    }
+
+    fn available_in(&self, mode: PathMode) -> bool {
+        mode == PathMode::Pragmatic
+    }
+
+    fn call(&self, args: &[FunctionValue], opts: QueryOptions) -> FunctionValue {
+        let (FunctionValue::Value(Some(a)), FunctionValue::Value(Some(b))) = (&args[0], &args[1])
+        else {
+            return FunctionValue::Logical(false);
+        };
+        let (a, b) = if opts.ignore_case {
+            (fold_case(a.clone()), fold_case(b.clone()))
+        } else {
+            (a.clone(), b.clone())
+        };
+        match (a, b) {
+            (Value::String(s), Value::String(part)) => {
+                FunctionValue::Logical((self.test)(&s, &part))
+            }
+            _ => FunctionValue::Logical(false),
+        }
+    }
+}
+
+/// `contains(value, x)`: the same test as the `contains` operator, i.e. whether an array has
+/// an element equal to `x` (as with `==` under the query's comparison mode) or a string has
+/// `x` as a substring. Not part of RFC 9535.
+struct Contains;
+
+impl FilterFunction for Contains {
+    fn name(&self) -> &str {
+        "contains"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value, FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Logical
+    }
+
+    fn available_in(&self, mode: PathMode) -> bool {
+        mode == PathMode::Pragmatic
+    }
+
+    fn call(&self, args: &[FunctionValue], opts: QueryOptions) -> FunctionValue {
+        let (FunctionValue::Value(Some(a)), FunctionValue::Value(Some(b))) = (&args[0], &args[1])
+        else {
+            return FunctionValue::Logical(false);
+        };
+        let holds = if opts.ignore_case {
+            SetOp::Contains.holds(
+                &fold_case(a.clone()),
+                &fold_case(b.clone()),
+                opts.comparison,
+            )
+        } else {
+            SetOp::Contains.holds(a, b, opts.comparison)
+        };
+        FunctionValue::Logical(holds)
+    }
+}
+
+/// `substring(value, start[, end])`: the characters from `start` up to, but not including,
+/// `end` (the end of the string by default). Positions count characters, negative positions
+/// count from the end, and out-of-range positions are clamped. Not part of RFC 9535.
+struct Substring;
+
+impl FilterFunction for Substring {
+    fn name(&self) -> &str {
+        "substring"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[
+            FunctionType::Value,
+            FunctionType::Value,
+            FunctionType::Value,
+        ]
+    }
+
+    fn required_params(&self) -> usize {
+        2
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn available_in(&self, mode: PathMode) -> bool {
+        mode == PathMode::Pragmatic
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        let position = |arg: Option<&FunctionValue>| match arg {
+            Some(FunctionValue::Value(Some(v))) => v.as_i64(),
+            _ => None,
+        };
+        let FunctionValue::Value(Some(Value::String(s))) = &args[0] else {
+            return FunctionValue::Value(None);
+        };
+        let chars: Vec<char> = s.chars().collect();
+        let len = chars.len() as i64;
+        let clamp = |i: i64| (if i < 0 { len + i } else { i }).clamp(0, len) as usize;
+        let end = match args.get(2) {
+            None => Some(len),
+            arg => position(arg),
+        };
+        let (Some(start), Some(end)) = (position(args.get(1)), end) else {
+            return FunctionValue::Value(None);
+        };
+        let (start, end) = (clamp(start), clamp(end));
+        let out: String = chars[start..end.max(start)].iter().collect();
+        FunctionValue::Value(Some(Value::String(out)))
+    }
+}
+
+/// `exists(nodes)`: whether the query selects at least one node, even one whose value is
+/// `null` or `false`. Not part of RFC 9535, where a bare query is an existence test.
+struct Exists;
+
+impl FilterFunction for Exists {
+    fn name(&self) -> &str {
+        "exists"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Nodes]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Logical
+    }
+
+    fn available_in(&self, mode: PathMode) -> bool {
+        mode == PathMode::Pragmatic
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        FunctionValue::Logical(matches!(&args[0], FunctionValue::Nodes(nodes) if !nodes.is_empty()))
+    }
+}
+
+/// `type_of(value)`: the JSON type of the value as a string: `"null"`, `"boolean"`,
+/// `"number"`, `"string"`, `"array"` or `"object"`; Nothing when the value is missing.
+/// Not part of RFC 9535.
+struct TypeOf;
+
+impl FilterFunction for TypeOf {
+    fn name(&self) -> &str {
+        "type_of"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn available_in(&self, mode: PathMode) -> bool {
+        mode == PathMode::Pragmatic
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        let FunctionValue::Value(Some(v)) = &args[0] else {
+            return FunctionValue::Value(None);
+        };
+        let name = match v {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        FunctionValue::Value(Some(Value::from(name)))
+    }
+}
+
+/// Type predicates: `is_null(value)`, `is_boolean(value)`, `is_number(value)`,
+/// `is_string(value)`, `is_array(value)` and `is_object(value)`. False when the value is
+/// missing (Nothing), so `is_null` only holds for an explicit `null`. Not part of RFC 9535.
+struct TypeTest {
+    name: &'static str,
+    test: fn(&Value) -> bool,
+}
+
+impl FilterFunction for TypeTest {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Logical
+    }
+
+    fn available_in(&self, mode: PathMode) -> bool {
+        mode == PathMode::Pragmatic
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        FunctionValue::Logical(matches!(&args[0], FunctionValue::Value(Some(v)) if (self.test)(v)))
+    }
+}
@@ -1,14 +1,89 @@
+use crate::comparison::ComparisonMode;
 use crate::filter::FilterExpr;
 use crate::parser::{ParseError, Parser};
 use serde_json::Value;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::str::FromStr;
 use tracing::error;
 
+/// Dialect used to parse and evaluate a JSONPath query.
+/// `Pragmatic` is the lenient dialect this crate has always spoken; `Rfc9535` follows
+/// RFC 9535 to the letter and is meant for queries shared with other implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathMode {
+    #[default]
+    Pragmatic, // Lenient dialect: `[?(...)]` filters, helpers, unwrapped single-array results
+    Rfc9535, // Strict RFC 9535 grammar and semantics, results are always a nodelist
+}
+
+/// Parses a mode name as accepted by the expression language (`from_json(json, path, "rfc9535")`).
+impl FromStr for PathMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pragmatic" => Ok(PathMode::Pragmatic),
+            "rfc9535" | "rfc" => Ok(PathMode::Rfc9535),
+            other => Err(format!("unknown JSONPath mode `{other}`")),
+        }
+    }
+}
+
+/// Options for evaluating a query, see `from_value_with_options`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// The JSONPath dialect.
+    pub mode: PathMode,
+    /// Compare strings in filters using Unicode case folding.
+    pub ignore_case: bool,
+    /// How pragmatic filters compare values of different types.
+    pub comparison: ComparisonMode,
+}
+
+impl QueryOptions {
+    /// Applies comma-separated option names on top of these options: a dialect
+    /// (`pragmatic`, `rfc9535`), a comparison mode (`lenient`, `strict`, `numeric_strings`)
+    /// and/or `ignore_case` (alias `i`).
+    pub fn apply_str(mut self, s: &str) -> Result<Self, String> {
+        for token in s.split(',').map(str::trim) {
+            match token {
+                "ignore_case" | "i" => self.ignore_case = true,
+                other => match other.parse::<ComparisonMode>() {
+                    Ok(comparison) => self.comparison = comparison,
+                    Err(_) => self.mode = other.parse()?,
+                },
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// Parses comma-separated options as accepted by the expression language
+/// (`from_json(json, path, "rfc9535,ignore_case")`), see `QueryOptions::apply_str`.
+impl FromStr for QueryOptions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        QueryOptions::default().apply_str(s)
+    }
+}
+
 /// Represents a parsed JSONPath, consisting of a sequence of segments.
 #[derive(Debug, Clone)]
 pub struct Path {
     pub segments: Vec<Segment>,
 }
 
+impl Path {
+    /// Returns true when the path can select at most one node (only keys and indexes).
+    pub fn is_singular(&self) -> bool {
+        self.segments
+            .iter()
+            .all(|s| matches!(s, Segment::Root | Segment::Key(_) | Segment::Index(_)))
+    }
+}
+
 /// Enum for each possible segment in a JSONPath expression.
 #[derive(Debug, Clone)]
 pub enum Segment {
@@ -23,6 +98,9 @@ pub enum Segment {
     }, // `[start:end:step]` - array slicing
     Recursive,   // `..` - recursive descent
     Filter(Box<FilterExpr>), // `[?(expr)]` - filter expression
+    Union(Vec<Segment>), // `['a','b']`, `[0,2:4,?(expr)]` - several selectors in one bracket
+    Parent,      // `^` - the node each node was selected from
+    KeyName,     // `~` - the member name or index of each node (last segment only)
 }
 
 pub type ParseErr = ParseError;
@@ -30,9 +108,31 @@ pub type ParseErr = ParseError;
 /// Entry point: evaluates a JSONPath string against a JSON value.
 /// Returns the matched values as a JSON array, or Null if no match.
 pub fn from_value(data: &Value, path: &str) -> Value {
-    match parse_path(path) {
+    from_value_with_mode(data, path, PathMode::Pragmatic)
+}
+
+/// Evaluates a JSONPath string against a JSON value using the given dialect.
+/// In `Rfc9535` mode the result is always the nodelist as a JSON array (possibly empty);
+/// `Null` is only returned when the path does not parse.
+pub fn from_value_with_mode(data: &Value, path: &str, mode: PathMode) -> Value {
+    from_value_with_options(
+        data,
+        path,
+        QueryOptions {
+            mode,
+            ..QueryOptions::default()
+        },
+    )
+}
+
+/// Evaluates a JSONPath string against a JSON value with the given query options.
+pub fn from_value_with_options(data: &Value, path: &str, opts: QueryOptions) -> Value {
+    match parse_path(path, opts.mode) {
         Ok(ast) => {
-            let refs = eval_path(data, &ast);
+            let refs = eval_path(data, &ast, opts);
+            if opts.mode == PathMode::Rfc9535 {
+                return Value::Array(refs.into_iter().map(Cow::into_owned).collect());
+            }
             if refs.is_empty() {
                 Value::Null
             } else {
@@ -43,11 +143,11 @@ pub fn from_value(data: &Value, path: &str) -> Value {
                 // selecting multiple elements (e.g. wildcard / recursive descent) still returns
                 // a flat array of matches.
                 if refs.len() == 1 {
-                    if let Value::Array(_) = refs[0] {
-                        return refs[0].clone();
+                    if let Value::Array(_) = *refs[0] {
+                        return refs[0].clone().into_owned();
                     }
                 }
-                Value::Array(refs.into_iter().cloned().collect())
+                Value::Array(refs.into_iter().map(Cow::into_owned).collect())
             }
         }
         Err(e) => {
@@ -59,51 +159,171 @@ pub fn from_value(data: &Value, path: &str) -> Value {
 }
 
 /// Parses a JSONPath string into a Path AST.
-fn parse_path(input: &str) -> Result<Path, ParseErr> {
-    let mut p = PathParser::new(input);
+fn parse_path(input: &str, mode: PathMode) -> Result<Path, ParseErr> {
+    let mut parser = Parser::new(input);
+    let mut p = PathParser::new(&mut parser, mode);
     p.parse()
 }
 
+/// Parses a `$`-rooted query embedded in a larger expression (e.g. a filter operand),
+/// stopping after the last segment and leaving the rest of the input to the caller.
+pub fn parse_root_query(parser: &mut Parser, mode: PathMode) -> Result<Path, ParseErr> {
+    PathParser::new(parser, mode).parse_query()
+}
+
+/// Parses an `@`-relative query inside a filter expression, with the same segment grammar
+/// as `$` queries.
+pub fn parse_relative_query(parser: &mut Parser, mode: PathMode) -> Result<Path, ParseErr> {
+    PathParser::new(parser, mode).parse_relative_query()
+}
+
+/// Skips insignificant whitespace: any Unicode whitespace in pragmatic mode,
+/// only the RFC 9535 blank characters in strict mode.
+pub fn skip_s(parser: &mut Parser, mode: PathMode) {
+    match mode {
+        PathMode::Pragmatic => parser.skip_ws(),
+        PathMode::Rfc9535 => parser.skip_blank(),
+    }
+}
+
+/// Parses a quoted string literal using the escape rules of the given dialect.
+pub fn parse_string(parser: &mut Parser, mode: PathMode) -> Result<String, ParseErr> {
+    match mode {
+        PathMode::Pragmatic => parser.parse_quoted_string(),
+        PathMode::Rfc9535 => parser.parse_quoted_string_strict(),
+    }
+}
+
+/// Parses a member name after `.` using the identifier rules of the given dialect.
+fn parse_name(parser: &mut Parser, mode: PathMode) -> Result<String, ParseErr> {
+    match mode {
+        PathMode::Pragmatic => parser.parse_identifier(),
+        PathMode::Rfc9535 => parser.parse_member_name(),
+    }
+}
+
+/// Parses an array index using the integer rules of the given dialect.
+fn parse_index(parser: &mut Parser, mode: PathMode) -> Result<i64, ParseErr> {
+    match mode {
+        PathMode::Pragmatic => parser.parse_int(),
+        PathMode::Rfc9535 => parser.parse_int_strict(),
+    }
+}
+
 /// Parser for JSONPath strings.
-pub struct PathParser<'a> {
-    parser: Parser<'a>,
+/// Borrows the underlying `Parser` so that paths can be parsed inside filter expressions.
+pub struct PathParser<'p, 'a> {
+    parser: &'p mut Parser<'a>,
+    mode: PathMode,
 }
 
-impl<'a> PathParser<'a> {
-    /// Creates a new PathParser from a string slice.
-    pub fn new(s: &'a str) -> Self {
-        Self {
-            parser: Parser::new(s),
-        }
+impl<'p, 'a> PathParser<'p, 'a> {
+    /// Creates a new PathParser reading from the given parser.
+    pub fn new(parser: &'p mut Parser<'a>, mode: PathMode) -> Self {
+        Self { parser, mode }
+    }
+
+    /// Returns true when parsing with strict RFC 9535 rules.
+    fn strict(&self) -> bool {
+        self.mode == PathMode::Rfc9535
     }
 
     /// Parses the full path, returning a Path AST.
     fn parse(&mut self) -> Result<Path, ParseErr> {
-        let mut segments = Vec::new();
-        self.parser.skip_ws();
+        // RFC 9535 does not allow leading whitespace
+        if !self.strict() {
+            self.parser.skip_ws();
+        }
+        let path = self.parse_query()?;
+        // RFC 9535 does not allow trailing input either
+        if self.strict() && !self.parser.eof() {
+            return Err(ParseErr::InvalidSyntax("unexpected trailing input".into()));
+        }
+        Ok(path)
+    }
+
+    /// Parses `$` followed by as many segments as possible.
+    fn parse_query(&mut self) -> Result<Path, ParseErr> {
         // Path must start with `$`
         if !self.parser.consume_char('$') {
             return Err(ParseErr::InvalidSyntax("path must start with `$`".into()));
         }
-        segments.push(Segment::Root);
+        let mut segments = vec![Segment::Root];
+        self.parse_segments(&mut segments)?;
+        Ok(Path { segments })
+    }
+
+    /// Parses `@` followed by as many segments as possible.
+    /// The resulting path has no `Root` segment and is evaluated from the current node.
+    fn parse_relative_query(&mut self) -> Result<Path, ParseErr> {
+        if !self.parser.consume_char('@') {
+            return Err(ParseErr::InvalidSyntax(
+                "relative path must start with `@`".into(),
+            ));
+        }
+        let mut segments = Vec::new();
+        self.parse_segments(&mut segments)?;
+        Ok(Path { segments })
+    }
 
+    /// Appends segments until the input no longer continues the path.
+    fn parse_segments(&mut self, segments: &mut Vec<Segment>) -> Result<(), ParseErr> {
         // Parse each segment until end of input
         while !self.parser.eof() {
-            self.parser.skip_ws();
+            let before = self.parser.pos();
+            // A descendant segment is glued to its selector in RFC 9535 (`..name`, not `.. name`)
+            if !(self.strict() && matches!(segments.last(), Some(Segment::Recursive))) {
+                skip_s(self.parser, self.mode);
+            }
 
             if let Some(segment) = self.parse_next_segment(segments.last())? {
                 segments.push(segment);
             } else {
+                self.parser.set_pos(before);
                 break;
             }
         }
-        Ok(Path { segments })
+
+        if self.strict() && matches!(segments.last(), Some(Segment::Recursive)) {
+            return Err(ParseErr::InvalidSyntax(
+                "descendant segment requires a selector".into(),
+            ));
+        }
+        // Key names are not nodes, so nothing can follow `~`
+        if let Some(i) = segments.iter().position(|s| matches!(s, Segment::KeyName)) {
+            if i + 1 != segments.len() {
+                return Err(ParseErr::InvalidSyntax(
+                    "`~` must be the last segment".into(),
+                ));
+            }
+        }
+        Ok(())
     }
 
     /// Parses the next segment in the path.
     /// `prev` provides the previously parsed segment (if any) allowing context-sensitive parsing
     /// for cases like recursive descent where a bare identifier or wildcard may follow (`$..name`).
     fn parse_next_segment(&mut self, prev: Option<&Segment>) -> Result<Option<Segment>, ParseErr> {
+        // RFC 9535: `..` must be followed by a bracketed selection, `*` or a member name
+        if self.strict() && matches!(prev, Some(Segment::Recursive)) {
+            if self.parser.consume_char('[') {
+                return self.parse_bracket_segment();
+            }
+            if self.parser.consume_char('*') {
+                return Ok(Some(Segment::Wildcard));
+            }
+            let key = self.parser.parse_member_name()?;
+            return Ok(Some(Segment::Key(key)));
+        }
+
+        // Parent and key name operators: `^`, `~` (JSONPath-Plus, not part of RFC 9535)
+        if !self.strict() && self.parser.consume_char('^') {
+            return Ok(Some(Segment::Parent));
+        }
+        if !self.strict() && self.parser.consume_char('~') {
+            return Ok(Some(Segment::KeyName));
+        }
+
         // Recursive descent: `..`
         if self.parser.peek_str("..") {
             self.parser.consume_char('.');
@@ -145,169 +365,331 @@ impl<'a> PathParser<'a> {
         if self.parser.consume_char('*') {
             Ok(Some(Segment::Wildcard))
         } else {
-            let key = self.parser.parse_identifier()?;
+            let key = parse_name(self.parser, self.mode)?;
             Ok(Some(Segment::Key(key)))
         }
     }
 
-    /// Parses a bracket segment: wildcard, filter, key, index, or slice.
+    /// Parses a bracket segment: wildcard, filter, key, index, slice, or a comma-separated
+    /// list of those, which becomes a union.
     fn parse_bracket_segment(&mut self) -> Result<Option<Segment>, ParseErr> {
-        self.parser.skip_ws();
+        let mut selectors = Vec::new();
+        loop {
+            skip_s(self.parser, self.mode);
+            selectors.push(self.parse_selector()?);
+            skip_s(self.parser, self.mode);
+            if !self.parser.consume_char(',') {
+                break;
+            }
+        }
+        self.parser.expect(']')?;
+        if selectors.len() == 1 {
+            Ok(selectors.pop())
+        } else {
+            Ok(Some(Segment::Union(selectors)))
+        }
+    }
 
+    /// Parses a single selector inside brackets.
+    fn parse_selector(&mut self) -> Result<Segment, ParseErr> {
         // Wildcard: `[*]`
         if self.parser.consume_char('*') {
-            self.parser.expect(']')?;
-            return Ok(Some(Segment::Wildcard));
+            return Ok(Segment::Wildcard);
         }
 
-        // Filter: `[?(expr)]`
+        // Filter: `[?(expr)]` or `[?expr]`
         if self.parser.peek_char() == Some('?') {
-            return self.parse_filter_segment();
+            return self.parse_filter_selector();
         }
 
         // Quoted key: `['key']` or `["key"]`
         if matches!(self.parser.peek_char(), Some('\'') | Some('"')) {
-            let key = self.parser.parse_quoted_string()?;
-            self.parser.expect(']')?;
-            return Ok(Some(Segment::Key(key)));
+            let key = parse_string(self.parser, self.mode)?;
+            return Ok(Segment::Key(key));
         }
 
         // Index or slice: `[0]`, `[1:3]`, `[1:3:2]`
-        self.parse_index_or_slice_segment()
+        self.parse_index_or_slice_selector()
     }
 
-    /// Parses a filter segment: `[?(expr)]`
-    fn parse_filter_segment(&mut self) -> Result<Option<Segment>, ParseErr> {
+    /// Parses a filter selector: `?` followed by a logical expression.
+    /// The pragmatic `?(expr)` form is simply a parenthesized expression.
+    fn parse_filter_selector(&mut self) -> Result<Segment, ParseErr> {
         self.parser.consume_char('?');
-        self.parser.expect('(')?;
-        let expr = crate::filter::parse_filter_or(&mut self.parser)?;
-        self.parser.expect(')')?;
-        self.parser.expect(']')?;
-        Ok(Some(Segment::Filter(Box::new(expr))))
+        let expr = crate::filter::parse_filter_or(self.parser, self.mode)?;
+        Ok(Segment::Filter(Box::new(expr)))
     }
 
-    /// Parses an index or slice segment.
-    fn parse_index_or_slice_segment(&mut self) -> Result<Option<Segment>, ParseErr> {
-        let slice_content = self.parser.capture_until(']')?;
-        self.parser.expect(']')?;
+    /// Parses an index or slice selector: `0`, `1:3`, `1:3:2`, `::-1`.
+    fn parse_index_or_slice_selector(&mut self) -> Result<Segment, ParseErr> {
+        let start = if self.at_int() {
+            Some(parse_index(self.parser, self.mode)?)
+        } else {
+            None
+        };
+        skip_s(self.parser, self.mode);
 
-        // Slice: contains `:`
-        if slice_content.contains(':') {
-            self.parse_slice(&slice_content)
+        // Index: single integer
+        if !self.parser.consume_char(':') {
+            return start
+                .map(Segment::Index)
+                .ok_or_else(|| ParseErr::InvalidSyntax("invalid selector".into()));
+        }
+
+        // Slice: `start:end[:step]`
+        skip_s(self.parser, self.mode);
+        let end = self.parse_slice_bound()?;
+        skip_s(self.parser, self.mode);
+        let step = if self.parser.consume_char(':') {
+            skip_s(self.parser, self.mode);
+            self.parse_slice_bound()?
         } else {
-            // Index: single integer
-            let mut tmp = Parser::new(&slice_content);
-            let idx = tmp.parse_int()?;
-            Ok(Some(Segment::Index(idx)))
+            None
+        };
+        Ok(Segment::Slice { start, end, step })
+    }
+
+    /// Parses an optional slice component; an omitted component is `None`.
+    fn parse_slice_bound(&mut self) -> Result<Option<i64>, ParseErr> {
+        if self.at_int() {
+            return parse_index(self.parser, self.mode)
+                .map(Some)
+                .map_err(|_| ParseErr::InvalidSyntax("bad slice number".into()));
+        }
+        match self.parser.peek_char() {
+            Some(':') | Some(']') | Some(',') | None => Ok(None),
+            Some(c) if c.is_whitespace() => Ok(None),
+            Some(_) => Err(ParseErr::InvalidSyntax("bad slice number".into())),
         }
     }
 
-    /// Parses a slice segment: `[start:end:step]`
-    fn parse_slice(&self, content: &str) -> Result<Option<Segment>, ParseErr> {
-        let parts: Vec<&str> = content.split(':').collect();
-        if parts.len() > 3 {
-            return Err(ParseErr::InvalidSyntax("slice too many components".into()));
+    /// Returns true when the next character can start an integer.
+    fn at_int(&self) -> bool {
+        matches!(self.parser.peek_char(), Some(c) if c == '-' || c.is_ascii_digit())
+    }
+}
+
+/// A node selected during evaluation: its value and where it was found, so that the
+/// parent (`^`) and key name (`~`) operators can look back up the document.
+#[derive(Debug, Clone)]
+struct Node<'a> {
+    value: &'a Value,
+    /// The node this one was selected from and the member name or index it sits under;
+    /// `None` where evaluation started (the root, or `@` in a filter).
+    parent: Option<(Rc<Node<'a>>, Step<'a>)>,
+}
+
+/// The member name or array index under which a node sits in its parent.
+#[derive(Debug, Clone, Copy)]
+enum Step<'a> {
+    Name(&'a str),
+    Index(usize),
+}
+
+impl<'a> Node<'a> {
+    /// A node with no known parent.
+    fn top(value: &'a Value) -> Self {
+        Node {
+            value,
+            parent: None,
         }
+    }
 
-        // Helper to parse optional i64 values
-        let parse_opt_i64 = |s: &str| -> Result<Option<i64>, ParseErr> {
-            let t = s.trim();
-            if t.is_empty() {
-                Ok(None)
-            } else {
-                t.parse::<i64>()
-                    .map(Some)
-                    .map_err(|_| ParseErr::InvalidSyntax("bad slice number".into()))
-            }
-        };
+    /// The children of an object or array, each pointing back at this node.
+    fn children(&self) -> Vec<Node<'a>> {
+        let parent = Rc::new(self.clone());
+        match self.value {
+            Value::Array(arr) => arr
+                .iter()
+                .enumerate()
+                .map(|(i, v)| parent.child(v, Step::Index(i)))
+                .collect(),
+            Value::Object(map) => map
+                .iter()
+                .map(|(k, v)| parent.child(v, Step::Name(k)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 
-        let start = parse_opt_i64(parts.get(0).copied().unwrap_or(""))?;
-        let end = parse_opt_i64(parts.get(1).copied().unwrap_or(""))?;
-        let step = parse_opt_i64(parts.get(2).copied().unwrap_or(""))?;
+    /// The array elements at the given indexes, each pointing back at this node.
+    fn elements(&self, arr: &'a [Value], indexes: Vec<usize>) -> Vec<Node<'a>> {
+        let parent = Rc::new(self.clone());
+        indexes
+            .into_iter()
+            .map(|i| parent.child(&arr[i], Step::Index(i)))
+            .collect()
+    }
 
-        Ok(Some(Segment::Slice { start, end, step }))
+    /// A single child of this node.
+    fn child(self: &Rc<Self>, value: &'a Value, step: Step<'a>) -> Node<'a> {
+        Node {
+            value,
+            parent: Some((Rc::clone(self), step)),
+        }
+    }
+
+    /// The member name or index of this node, as a JSON value.
+    fn key_name(&self) -> Option<Value> {
+        self.parent.as_ref().map(|(_, step)| match step {
+            Step::Name(name) => Value::from(*name),
+            Step::Index(i) => Value::from(*i),
+        })
     }
 }
 
 /// Evaluates a parsed Path AST against a JSON value.
-/// Returns a vector of references to matched values.
-fn eval_path<'a>(root: &'a Value, path: &Path) -> Vec<&'a Value> {
-    let mut current: Vec<&Value> = vec![root];
+/// Returns the matched values: references into the document, or the key names selected
+/// by a final `~`.
+pub fn eval_path<'a>(root: &'a Value, path: &Path, opts: QueryOptions) -> Vec<Cow<'a, Value>> {
+    eval_relative_path(root, root, path, opts)
+}
+
+/// Evaluates a Path AST starting from `current`; `root` is still used for `$` segments.
+pub fn eval_relative_path<'a>(
+    current: &'a Value,
+    root: &'a Value,
+    path: &Path,
+    opts: QueryOptions,
+) -> Vec<Cow<'a, Value>> {
+    let mut nodes = vec![Node::top(current)];
     for seg in &path.segments {
-        current = eval_segment(&current, seg, root);
+        if let Segment::KeyName = seg {
+            // Parsing only allows `~` as the last segment
+            return nodes
+                .iter()
+                .filter_map(Node::key_name)
+                .map(Cow::Owned)
+                .collect();
+        }
+        nodes = eval_segment(&nodes, seg, root, opts);
     }
-    current
+    nodes.into_iter().map(|n| Cow::Borrowed(n.value)).collect()
 }
 
-/// Evaluates a single segment against the current set of values.
-fn eval_segment<'a>(current: &[&'a Value], segment: &Segment, root: &'a Value) -> Vec<&'a Value> {
+/// Evaluates a single segment against the current set of nodes.
+fn eval_segment<'a>(
+    current: &[Node<'a>],
+    segment: &Segment,
+    root: &'a Value,
+    opts: QueryOptions,
+) -> Vec<Node<'a>> {
     match segment {
-        Segment::Root => vec![root],
+        Segment::Root => vec![Node::top(root)],
         Segment::Key(k) => eval_key_segment(current, k),
         Segment::Index(i) => eval_index_segment(current, *i),
-        Segment::Slice { start, end, step } => eval_slice_segment(current, *start, *end, *step),
+        Segment::Slice { start, end, step } => {
+            eval_slice_segment(current, *start, *end, *step, opts.mode)
+        }
         Segment::Wildcard => eval_wildcard_segment(current),
         Segment::Recursive => eval_recursive_segment(current),
-        Segment::Filter(expr) => eval_filter_segment(current, expr),
+        Segment::Filter(expr) => eval_filter_segment(current, expr, root, opts),
+        Segment::Union(selectors) => eval_union_segment(current, selectors, root, opts),
+        Segment::Parent => eval_parent_segment(current),
+        // Handled by `eval_relative_path`, as it yields names rather than nodes
+        Segment::KeyName => Vec::new(),
     }
 }
 
+/// Evaluates a union segment: for each node, applies every selector in order and
+/// concatenates the results (duplicates are kept).
+fn eval_union_segment<'a>(
+    current: &[Node<'a>],
+    selectors: &[Segment],
+    root: &'a Value,
+    opts: QueryOptions,
+) -> Vec<Node<'a>> {
+    current
+        .iter()
+        .flat_map(|n| {
+            selectors
+                .iter()
+                .flat_map(move |sel| eval_segment(std::slice::from_ref(n), sel, root, opts))
+        })
+        .collect()
+}
+
 /// Evaluates a key segment: gets the value for the given key from each object.
-fn eval_key_segment<'a>(current: &[&'a Value], key: &str) -> Vec<&'a Value> {
+fn eval_key_segment<'a>(current: &[Node<'a>], key: &str) -> Vec<Node<'a>> {
     current
         .iter()
-        .filter_map(|v| match v {
-            Value::Object(map) => map.get(key),
+        .filter_map(|n| match n.value {
+            Value::Object(map) => map
+                .get_key_value(key)
+                .map(|(k, v)| Rc::new(n.clone()).child(v, Step::Name(k))),
             _ => None,
         })
         .collect()
 }
 
 /// Evaluates an index segment: gets the value at the given index from each array.
-fn eval_index_segment<'a>(current: &[&'a Value], index: i64) -> Vec<&'a Value> {
-    if index < 0 {
-        return Vec::new();
-    }
-
-    let idx = index as usize;
+/// Negative indexes count from the end of the array (`-1` is the last element).
+fn eval_index_segment<'a>(current: &[Node<'a>], index: i64) -> Vec<Node<'a>> {
     current
         .iter()
-        .filter_map(|v| match v {
-            Value::Array(arr) => arr.get(idx),
+        .filter_map(|n| match n.value {
+            Value::Array(arr) => {
+                array_index(arr, index).map(|i| Rc::new(n.clone()).child(&arr[i], Step::Index(i)))
+            }
             _ => None,
         })
         .collect()
 }
 
+/// Resolves an index, counting from the end for negative indexes, if it is in range.
+fn array_index(arr: &[Value], index: i64) -> Option<usize> {
+    let idx = if index < 0 {
+        arr.len() as i64 + index
+    } else {
+        index
+    };
+    usize::try_from(idx).ok().filter(|&i| i < arr.len())
+}
+
 /// Evaluates a slice segment: gets a slice of values from each array.
 fn eval_slice_segment<'a>(
-    current: &[&'a Value],
+    current: &[Node<'a>],
     start: Option<i64>,
     end: Option<i64>,
     step: Option<i64>,
-) -> Vec<&'a Value> {
+    mode: PathMode,
+) -> Vec<Node<'a>> {
     current
         .iter()
-        .flat_map(|v| match v {
-            Value::Array(arr) => slice_array(arr, start, end, step),
+        .flat_map(|n| match (n.value, mode) {
+            (Value::Array(arr), PathMode::Pragmatic) => {
+                n.elements(arr, slice_array(arr, start, end, step))
+            }
+            (Value::Array(arr), PathMode::Rfc9535) => {
+                n.elements(arr, slice_array_rfc(arr, start, end, step))
+            }
             _ => Vec::new(),
         })
         .collect()
 }
 
 /// Evaluates a wildcard segment: gets all child values from each object or array.
-fn eval_wildcard_segment<'a>(current: &[&'a Value]) -> Vec<&'a Value> {
-    current.iter().flat_map(|v| get_child_values(v)).collect()
+fn eval_wildcard_segment<'a>(current: &[Node<'a>]) -> Vec<Node<'a>> {
+    current.iter().flat_map(Node::children).collect()
+}
+
+/// Evaluates a parent segment (`^`): the node each node was selected from. Nodes without
+/// a parent (the root, or `@` in a filter) select nothing; duplicates are kept.
+fn eval_parent_segment<'a>(current: &[Node<'a>]) -> Vec<Node<'a>> {
+    current
+        .iter()
+        .filter_map(|n| n.parent.as_ref().map(|(parent, _)| Node::clone(parent)))
+        .collect()
 }
 
 /// Evaluates a recursive segment: collects all descendant nodes that can be searched.
 /// This implements proper JSONPath recursive descent semantics.
-fn eval_recursive_segment<'a>(current: &[&'a Value]) -> Vec<&'a Value> {
+fn eval_recursive_segment<'a>(current: &[Node<'a>]) -> Vec<Node<'a>> {
     let mut result = Vec::new();
 
-    for &value in current {
+    for node in current {
         // Only collect nodes that can meaningfully have keys applied to them
-        collect_searchable_nodes(value, &mut result);
+        collect_searchable_nodes(node.clone(), &mut result);
     }
 
     result
@@ -316,66 +698,47 @@ fn eval_recursive_segment<'a>(current: &[&'a Value]) -> Vec<&'a Value> {
 /// Collects all descendant nodes that could be targets for subsequent path segments.
 /// This includes the current node and all nested objects and arrays, but excludes
 /// primitive values that cannot have keys applied to them.
-fn collect_searchable_nodes<'a>(value: &'a Value, result: &mut Vec<&'a Value>) {
-    match value {
-        Value::Object(_) => {
-            // Objects can have keys applied to them
-            result.push(value);
-            // Recurse into object values
-            if let Value::Object(obj) = value {
-                for child_value in obj.values() {
-                    collect_searchable_nodes(child_value, result);
-                }
-            }
-        }
-        Value::Array(arr) => {
-            // Arrays can have indices applied, but we also need to search their contents
-            result.push(value);
-            // Recurse into array elements
-            for child_value in arr.iter() {
-                collect_searchable_nodes(child_value, result);
-            }
-        }
-        _ => {
-            // Primitive values (strings, numbers, booleans, null) cannot have
-            // keys applied to them, so we don't include them in recursive descent
-        }
+fn collect_searchable_nodes<'a>(node: Node<'a>, result: &mut Vec<Node<'a>>) {
+    // Primitive values (strings, numbers, booleans, null) cannot have
+    // keys applied to them, so we don't include them in recursive descent
+    if !matches!(node.value, Value::Object(_) | Value::Array(_)) {
+        return;
+    }
+    // Objects can have keys applied to them, arrays indices; we also search their contents
+    let children = node.children();
+    result.push(node);
+    for child in children {
+        collect_searchable_nodes(child, result);
     }
 }
 
 /// Evaluates a filter segment: filters values using the filter expression.
-fn eval_filter_segment<'a>(current: &[&'a Value], expr: &FilterExpr) -> Vec<&'a Value> {
+/// RFC 9535 filters the children of arrays and objects; the pragmatic dialect filters
+/// array elements, or the value itself when it is not an array.
+fn eval_filter_segment<'a>(
+    current: &[Node<'a>],
+    expr: &FilterExpr,
+    root: &'a Value,
+    opts: QueryOptions,
+) -> Vec<Node<'a>> {
     current
         .iter()
-        .flat_map(|v| get_filterable_values(v))
-        .filter(|v| crate::filter::eval_filter(expr, v))
+        .flat_map(|n| match (opts.mode, n.value) {
+            (PathMode::Pragmatic, Value::Array(_)) | (PathMode::Rfc9535, _) => n.children(),
+            (PathMode::Pragmatic, _) => vec![n.clone()],
+        })
+        .filter(|n| crate::filter::eval_filter(expr, n.value, root, opts))
         .collect()
 }
 
-/// Gets all child values of an object or array.
-fn get_child_values(value: &Value) -> Vec<&Value> {
-    match value {
-        Value::Array(arr) => arr.iter().collect(),
-        Value::Object(map) => map.values().collect(),
-        _ => Vec::new(),
-    }
-}
-
-/// Gets values that can be filtered: array elements or the value itself.
-fn get_filterable_values(value: &Value) -> Vec<&Value> {
-    match value {
-        Value::Array(arr) => arr.iter().collect(),
-        _ => vec![value],
-    }
-}
-
 /// Slices an array according to start, end, and step parameters.
+/// Returns the indexes of the selected elements.
 fn slice_array(
-    arr: &Vec<Value>,
+    arr: &[Value],
     start: Option<i64>,
     end: Option<i64>,
     step: Option<i64>,
-) -> Vec<&Value> {
+) -> Vec<usize> {
     let n = arr.len() as i64;
     let step = step.unwrap_or(1);
     if step == 0 {
@@ -397,27 +760,29 @@ fn slice_array(
     );
 
     if step > 0 {
-        slice_forward(arr, lo, hi, step)
+        slice_forward(lo, hi, step)
     } else {
-        slice_backward(arr, lo, hi, step, n)
+        slice_backward(lo, hi, step, n)
     }
 }
 
 /// Slices an array forward (step > 0).
-fn slice_forward(arr: &Vec<Value>, lo: i64, hi: i64, step: i64) -> Vec<&Value> {
+fn slice_forward(lo: i64, hi: i64, step: i64) -> Vec<usize> {
     let mut out = Vec::new();
     let mut i = lo;
     while i < hi {
-        if let Some(v) = arr.get(i as usize) {
-            out.push(v);
-        }
-        i += step;
+        out.push(i as usize);
+        // A step past i64::MAX ends the slice
+        let Some(next) = i.checked_add(step) else {
+            break;
+        };
+        i = next;
     }
     out
 }
 
 /// Slices an array backward (step < 0).
-fn slice_backward(arr: &Vec<Value>, lo: i64, hi: i64, step: i64, n: i64) -> Vec<&Value> {
+fn slice_backward(lo: i64, hi: i64, step: i64, n: i64) -> Vec<usize> {
     let mut out = Vec::new();
     if hi == 0 {
         return out;
@@ -425,12 +790,50 @@ fn slice_backward(arr: &Vec<Value>, lo: i64, hi: i64, step: i64, n: i64) -> Vec<
 
     let mut i = (hi - 1).clamp(0, n - 1);
     while i >= lo {
-        if let Some(v) = arr.get(i as usize) {
-            out.push(v);
+        out.push(i as usize);
+        match i.checked_add(step) {
+            Some(next) if next >= 0 => i = next,
+            _ => break,
         }
-        i += step;
-        if i < 0 {
-            break;
+    }
+    out
+}
+
+/// Slices an array following RFC 9535 section 2.3.4.2.2: negative bounds count from the
+/// end, and for a negative step the defaults run from the last element to the first.
+/// Returns the indexes of the selected elements.
+fn slice_array_rfc(
+    arr: &[Value],
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+) -> Vec<usize> {
+    let len = arr.len() as i64;
+    let step = step.unwrap_or(1);
+    let normalize = |i: i64| if i >= 0 { i } else { len + i };
+
+    let mut out = Vec::new();
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+        let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+        let mut i = lower;
+        while i < upper {
+            out.push(i as usize);
+            let Some(next) = i.checked_add(step) else {
+                break;
+            };
+            i = next;
+        }
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+        let lower = normalize(end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+        let mut i = upper;
+        while lower < i {
+            out.push(i as usize);
+            let Some(next) = i.checked_add(step) else {
+                break;
+            };
+            i = next;
         }
     }
     out
@@ -1,36 +1,63 @@
+mod arithmetic; // Arithmetic on JSON numbers for filter operands
 mod comparison; // Handles comparison operations for expressions
 pub mod engine; // Core engine logic, exposed publicly
 pub mod errors; // Error types and result handling, exposed publicly
 mod expression; // Expression parsing and evaluation logic
 mod filter; // Filtering logic for data structures
+pub mod functions; // Filter function extensions, exposed publicly
 mod jsonpath; // JSONPath query support
 mod parser; // Parsing utilities
+mod pattern; // Regular expressions used by filters
 
 use errors::{EvalError, Result}; // Import custom error and result types
 use serde_json::Value; // JSON value type from serde_json
+use std::collections::HashMap; // Variables passed to `Evaluator::eval_with`
 
 /// The main evaluator struct.
 /// Provides methods to evaluate expressions and return results.
-pub struct Evaluator;
+#[derive(Default)]
+pub struct Evaluator {
+    /// Default options for the JSONPath queries run by `from_json`.
+    options: QueryOptions,
+}
 
 impl Evaluator {
     /// Creates a new Evaluator instance.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Sets how filters compare values of different types (`ComparisonMode::Lenient` by
+    /// default). A mode given in the options argument of `from_json` takes precedence.
+    pub fn with_comparison_mode(mut self, mode: ComparisonMode) -> Self {
+        self.options.comparison = mode;
+        self
     }
 
     /// Evaluates a string expression and returns a Result<Value>.
     /// If parsing fails, returns an EvalError::Parse.
     /// Delegates parsing and evaluation to the expression module.
     pub fn eval(&self, expr: &str) -> Result<Value> {
+        self.eval_with(expr, &HashMap::new())
+    }
+
+    /// Evaluates a string expression with external variables, which the expression refers
+    /// to as `$name` (e.g. `first(query($input, "$.a"))`). A `let` binding of the same
+    /// name shadows an external variable. Returns an EvalError::Runtime when the expression
+    /// refers to a variable that is bound neither way.
+    pub fn eval_with(&self, expr: &str, vars: &HashMap<String, Value>) -> Result<Value> {
         // Parse the expression string into an AST (Abstract Syntax Tree)
         let ast = match expression::parse_expr(expr) {
             Ok(ast) => ast,
             // On parse error, wrap the error in EvalError::Parse and return
             Err(e) => return Err(EvalError::Parse(format!("{e:?}"))),
         };
+        // A variable that neither a let nor the caller defines is most likely a typo
+        if let Some(name) = expression::unbound_var(&ast, vars) {
+            return Err(EvalError::Runtime(format!("unbound variable ${name}")));
+        }
         // Evaluate the AST and return the resulting value
-        let value = expression::eval_ast(&ast);
+        let value = expression::eval_ast(&ast, &mut expression::Env::new(self.options, vars));
         Ok(value)
     }
 }
@@ -50,4 +77,17 @@ pub fn eval_coerce_null(expr: &str) -> Value {
 
 /// Re-export commonly used helpers from the engine module for convenience.
 /// These functions can be called directly by users of this library.
-pub use engine::{first, from_json, or_default, unique};
+pub use engine::{
+    first, from_json, from_json_with_mode, from_json_with_options, or_default, or_default_value,
+    query, unique,
+};
+
+/// Filter function extension points, see `functions::FilterFunction`.
+pub use functions::{register_function, FilterFunction, FunctionType, FunctionValue};
+
+/// Comparison semantics of filters, see `Evaluator::with_comparison_mode`.
+pub use comparison::ComparisonMode;
+
+/// JSONPath dialect selector and query options, see `from_json_with_mode` and
+/// `from_json_with_options`.
+pub use jsonpath::{PathMode, QueryOptions};
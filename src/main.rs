@@ -1,5 +1,7 @@
 use clap::Parser; // Import clap for command-line argument parsing
 use json_path_like_value_retrieval as jpl; // Import the json_path_like_value_retrieval crate as jpl
+use serde_json::Value; // JSON values of --var and --var-file
+use std::collections::HashMap; // Variables passed to the evaluator
 use std::fs; // Import filesystem utilities
 use std::io::{self, Read}; // Import IO traits and types
 use tracing::{error, info};
@@ -17,6 +19,43 @@ struct Args {
     /// File containing the expression
     #[arg(long = "expr-file")]
     expr_file: Option<String>, // Optional path to a file containing the expression
+
+    /// Variable for the expression as name=<JSON>, referred to as $name (repeatable)
+    #[arg(long = "var", value_name = "NAME=JSON", value_parser = parse_var)]
+    vars: Vec<(String, Value)>,
+
+    /// Variable read from a JSON file as name=<path>, referred to as $name (repeatable)
+    #[arg(long = "var-file", value_name = "NAME=PATH", value_parser = parse_var_file)]
+    var_files: Vec<(String, Value)>,
+}
+
+// Parses a `name=<JSON>` variable argument
+fn parse_var(arg: &str) -> Result<(String, Value), String> {
+    let (name, json) = split_var(arg)?;
+    let value =
+        serde_json::from_str(json).map_err(|e| format!("invalid JSON for `{name}`: {e}"))?;
+    Ok((name.to_string(), value))
+}
+
+// Parses a `name=<path>` variable argument by reading and parsing the JSON file
+fn parse_var_file(arg: &str) -> Result<(String, Value), String> {
+    let (name, path) = split_var(arg)?;
+    let text = fs::read_to_string(path).map_err(|e| format!("cannot read `{path}`: {e}"))?;
+    let value =
+        serde_json::from_str(&text).map_err(|e| format!("invalid JSON in `{path}`: {e}"))?;
+    Ok((name.to_string(), value))
+}
+
+// Splits `name=rest` and checks that the name is an identifier
+fn split_var(arg: &str) -> Result<(&str, &str), String> {
+    let (name, rest) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got `{arg}`"))?;
+    let valid = !name.is_empty() && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric());
+    if !valid {
+        return Err(format!("invalid variable name `{name}`"));
+    }
+    Ok((name, rest))
 }
 
 // Reads all data from stdin and returns it as a String
@@ -59,8 +98,9 @@ fn main() {
         read_stdin().expect("failed to read expression from stdin")
     };
 
-    // Evaluate the expression using the jpl crate
-    match jpl::eval(&expr) {
+    // Evaluate the expression using the jpl crate, with the --var and --var-file variables
+    let vars: HashMap<String, Value> = args.vars.into_iter().chain(args.var_files).collect();
+    match jpl::Evaluator::new().eval_with(&expr, &vars) {
         Ok(v) => {
             // If successful, pretty-print the result as JSON via tracing (info level)
             match serde_json::to_string_pretty(&v) {
@@ -84,6 +84,90 @@ impl<'a> Parser<'a> {
             .map_err(|_| ParseError::InvalidSyntax("bad integer".into()))
     }
 
+    /// Parses an RFC 9535 integer: no leading zeros, no `-0`, and within the I-JSON
+    /// range of ±(2^53 - 1).
+    pub fn parse_int_strict(&mut self) -> Result<i64, ParseError> {
+        const MAX_SAFE_INT: i64 = (1 << 53) - 1;
+        let start = self.i;
+        let negative = self.consume_char('-');
+        let digits_start = self.i;
+        // Consume all digit characters
+        while let Some(c) = self.peek_char() {
+            if c.is_ascii_digit() {
+                self.i += 1;
+            } else {
+                break;
+            }
+        }
+        let digits = &self.s[digits_start..self.i];
+        if digits.is_empty() {
+            return Err(ParseError::InvalidSyntax("expected integer".into()));
+        }
+        // Leading zeros and negative zero are not valid integers
+        if (digits.len() > 1 && digits.starts_with('0')) || (negative && digits == "0") {
+            return Err(ParseError::InvalidSyntax(format!(
+                "invalid integer `{}`",
+                &self.s[start..self.i]
+            )));
+        }
+        match self.s[start..self.i].parse::<i64>() {
+            Ok(n) if (-MAX_SAFE_INT..=MAX_SAFE_INT).contains(&n) => Ok(n),
+            _ => Err(ParseError::InvalidSyntax("integer out of range".into())),
+        }
+    }
+
+    /// Parses a JSON number literal (optional minus, integer, fraction and exponent)
+    /// as defined by RFC 8259. Integers that fit in an `i64` stay integers.
+    pub fn parse_json_number(&mut self) -> Result<Value, ParseError> {
+        let start = self.i;
+        self.consume_char('-');
+        // Integer part: a single zero or a non-zero digit followed by digits
+        let int_start = self.i;
+        self.skip_digits();
+        let int_part = &self.s[int_start..self.i];
+        if int_part.is_empty() || (int_part.len() > 1 && int_part.starts_with('0')) {
+            return Err(ParseError::InvalidSyntax("invalid number".into()));
+        }
+        let mut is_float = false;
+        // Fraction part
+        if self.consume_char('.') {
+            is_float = true;
+            if self.skip_digits() == 0 {
+                return Err(ParseError::InvalidSyntax("invalid number fraction".into()));
+            }
+        }
+        // Exponent part
+        if self.consume_char('e') || self.consume_char('E') {
+            is_float = true;
+            if !self.consume_char('+') {
+                self.consume_char('-');
+            }
+            if self.skip_digits() == 0 {
+                return Err(ParseError::InvalidSyntax("invalid number exponent".into()));
+            }
+        }
+        let s = &self.s[start..self.i];
+        if !is_float {
+            if let Ok(i) = s.parse::<i64>() {
+                return Ok(Value::from(i));
+            }
+        }
+        number_value(s).ok_or_else(|| ParseError::InvalidSyntax("bad number".into()))
+    }
+
+    /// Consumes a run of ASCII digits and returns how many were consumed.
+    fn skip_digits(&mut self) -> usize {
+        let start = self.i;
+        while let Some(c) = self.peek_char() {
+            if c.is_ascii_digit() {
+                self.i += 1;
+            } else {
+                break;
+            }
+        }
+        self.i - start
+    }
+
     /// Parses a number literal (integer or float).
     /// Returns a `serde_json::Value` containing the number.
     pub fn parse_number_literal(&mut self) -> Result<Value, ParseError> {
@@ -116,18 +200,7 @@ impl<'a> Parser<'a> {
         if s.is_empty() {
             return Err(ParseError::InvalidSyntax("number expected".into()));
         }
-        // Parse as float if decimal point is present, otherwise as integer
-        if s.contains('.') {
-            let f: f64 = s
-                .parse()
-                .map_err(|_| ParseError::InvalidSyntax("bad float".into()))?;
-            Ok(Value::from(f))
-        } else {
-            let i: i64 = s
-                .parse()
-                .map_err(|_| ParseError::InvalidSyntax("bad int".into()))?;
-            Ok(Value::from(i))
-        }
+        number_value(s).ok_or_else(|| ParseError::InvalidSyntax("bad number".into()))
     }
 
     /// Parses a quoted string, handling escape sequences.
@@ -144,7 +217,7 @@ impl<'a> Parser<'a> {
         let mut out = String::new();
         // Loop until closing quote or end of input
         while let Some(c) = self.peek_char() {
-            self.i += 1;
+            self.i += c.len_utf8();
             if c == quote {
                 // Found closing quote
                 return Ok(out);
@@ -152,7 +225,7 @@ impl<'a> Parser<'a> {
             if c == '\\' {
                 // Handle escape sequences
                 if let Some(nc) = self.peek_char() {
-                    self.i += 1;
+                    self.i += nc.len_utf8();
                     match nc {
                         'n' => out.push('\n'),
                         't' => out.push('\t'),
@@ -179,23 +252,138 @@ impl<'a> Parser<'a> {
         Err(ParseError::InvalidSyntax("unterminated string".into()))
     }
 
-    /// Captures a substring until the specified end character is found.
-    /// Returns the substring or an error if the end character is missing.
-    pub fn capture_until(&mut self, end: char) -> Result<&'a str, ParseError> {
+    /// Parses an RFC 9535 string literal.
+    /// Only the escapes of RFC 9535 are accepted (`\b \f \n \r \t \/ \\ \uXXXX` and the
+    /// escaped enclosing quote), and unescaped control characters are rejected.
+    pub fn parse_quoted_string_strict(&mut self) -> Result<String, ParseError> {
+        let quote = match self.peek_char() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return Err(ParseError::InvalidSyntax("expected quoted string".into())),
+        };
+        self.i += 1; // Consume the opening quote
+        let mut out = String::new();
+        while let Some(c) = self.peek_char() {
+            self.i += c.len_utf8();
+            match c {
+                _ if c == quote => return Ok(out),
+                '\u{0}'..='\u{1f}' => {
+                    return Err(ParseError::InvalidSyntax(
+                        "unescaped control character in string".into(),
+                    ))
+                }
+                '\\' => {
+                    let nc = self
+                        .peek_char()
+                        .ok_or_else(|| ParseError::InvalidSyntax("unterminated string".into()))?;
+                    self.i += nc.len_utf8();
+                    match nc {
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        '/' => out.push('/'),
+                        '\\' => out.push('\\'),
+                        'u' => out.push(self.parse_unicode_escape()?),
+                        _ if nc == quote => out.push(nc),
+                        _ => {
+                            return Err(ParseError::InvalidSyntax(format!(
+                                "invalid escape `\\{nc}`"
+                            )))
+                        }
+                    }
+                }
+                _ => out.push(c),
+            }
+        }
+        Err(ParseError::InvalidSyntax("unterminated string".into()))
+    }
+
+    /// Parses the hex digits of a `\uXXXX` escape (the `\u` is already consumed),
+    /// combining a surrogate pair when a high surrogate is followed by `\uXXXX`.
+    fn parse_unicode_escape(&mut self) -> Result<char, ParseError> {
+        let hi = self.parse_hex4()?;
+        let code = match hi {
+            0xD800..=0xDBFF => {
+                if !self.peek_str("\\u") {
+                    return Err(ParseError::InvalidSyntax("unpaired surrogate".into()));
+                }
+                self.i += 2;
+                let lo = self.parse_hex4()?;
+                if !(0xDC00..=0xDFFF).contains(&lo) {
+                    return Err(ParseError::InvalidSyntax("invalid low surrogate".into()));
+                }
+                0x10000 + ((hi - 0xD800) << 10) + (lo - 0xDC00)
+            }
+            0xDC00..=0xDFFF => return Err(ParseError::InvalidSyntax("unpaired surrogate".into())),
+            _ => hi,
+        };
+        char::from_u32(code).ok_or_else(|| ParseError::InvalidSyntax("invalid escape".into()))
+    }
+
+    /// Parses exactly four hex digits.
+    fn parse_hex4(&mut self) -> Result<u32, ParseError> {
+        let hex = self
+            .s
+            .get(self.i..self.i + 4)
+            .filter(|h| h.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| ParseError::InvalidSyntax("invalid unicode escape".into()))?;
+        self.i += 4;
+        u32::from_str_radix(hex, 16)
+            .map_err(|_| ParseError::InvalidSyntax("invalid unicode escape".into()))
+    }
+
+    /// Parses an RFC 9535 member name shorthand: a letter, `_` or non-ASCII character,
+    /// followed by any of those or ASCII digits.
+    pub fn parse_member_name(&mut self) -> Result<String, ParseError> {
         let start = self.i;
-        // Loop until end character is found
         while let Some(c) = self.peek_char() {
-            if c == end {
+            let name_first = c == '_' || c.is_ascii_alphabetic() || !c.is_ascii();
+            if name_first || (self.i > start && c.is_ascii_digit()) {
+                self.i += c.len_utf8();
+            } else {
                 break;
             }
-            self.i += 1;
         }
-        // If end character not found, return error
-        if self.peek_char() != Some(end) {
-            return Err(ParseError::InvalidSyntax(format!("expected '{end}'")));
+        if self.i == start {
+            return Err(ParseError::InvalidSyntax("member name expected".into()));
+        }
+        Ok(self.s[start..self.i].to_string())
+    }
+
+    /// Parses a `/pattern/flags` regular expression literal.
+    /// `\/` stands for a literal slash; every other escape is kept for the regex engine.
+    /// Returns the pattern and the (possibly empty) flags.
+    pub fn parse_regex_literal(&mut self) -> Result<(String, String), ParseError> {
+        self.expect('/')?;
+        let mut pattern = String::new();
+        loop {
+            let c = self
+                .peek_char()
+                .ok_or_else(|| ParseError::InvalidSyntax("unterminated regex".into()))?;
+            self.i += c.len_utf8();
+            match c {
+                '/' => break,
+                '\\' if self.consume_char('/') => pattern.push('/'),
+                '\\' => {
+                    pattern.push(c);
+                    if let Some(nc) = self.peek_char() {
+                        self.i += nc.len_utf8();
+                        pattern.push(nc);
+                    }
+                }
+                _ => pattern.push(c),
+            }
+        }
+        let start = self.i;
+        while let Some(c) = self.peek_char() {
+            if c.is_ascii_alphabetic() {
+                self.i += 1;
+            } else {
+                break;
+            }
         }
-        // Return the captured substring
-        Ok(&self.s[start..self.i])
+        Ok((pattern, self.s[start..self.i].to_string()))
     }
 
     /// Expects the next character to match `c`, consuming it if so.
@@ -219,6 +407,17 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Consumes `lit` if the input continues with it.
+    /// Returns true if consumed, false otherwise (nothing is consumed then).
+    pub fn consume_str(&mut self, lit: &str) -> bool {
+        if self.peek_str(lit) {
+            self.i += lit.len();
+            true
+        } else {
+            false
+        }
+    }
+
     /// Peeks at the next character without consuming it.
     /// Returns `Some(char)` if available, otherwise `None`.
     pub fn peek_char(&self) -> Option<char> {
@@ -231,12 +430,29 @@ impl<'a> Parser<'a> {
         self.s[self.i..].starts_with(lit)
     }
 
+    /// Skips RFC 9535 blank characters (space, horizontal tab, line feed, carriage return).
+    pub fn skip_blank(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek_char() {
+            self.i += 1;
+        }
+    }
+
+    /// Returns the current position in the input.
+    pub fn pos(&self) -> usize {
+        self.i
+    }
+
+    /// Rewinds (or advances) the parser to a position previously returned by `pos`.
+    pub fn set_pos(&mut self, i: usize) {
+        self.i = i;
+    }
+
     /// Skips whitespace characters.
     /// Advances the index past any whitespace.
     pub fn skip_ws(&mut self) {
         while let Some(c) = self.peek_char() {
             if c.is_whitespace() {
-                self.i += 1;
+                self.i += c.len_utf8();
             } else {
                 break;
             }
@@ -249,3 +465,13 @@ impl<'a> Parser<'a> {
         self.i >= self.s.len()
     }
 }
+
+/// Converts number text to a JSON number without losing precision: integers beyond `i64`
+/// stay exact as `u64`, and with the `arbitrary_precision` feature the text is kept as is.
+/// Text that is not JSON (e.g. `1.`) is read as an `f64`.
+fn number_value(s: &str) -> Option<Value> {
+    s.parse::<serde_json::Number>()
+        .ok()
+        .or_else(|| serde_json::Number::from_f64(s.parse().ok()?))
+        .map(Value::Number)
+}
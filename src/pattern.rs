@@ -0,0 +1,67 @@
+use crate::parser::ParseError;
+use regex::{Regex, RegexBuilder};
+
+/// Compiles an I-Regexp (RFC 9485) pattern as used by the `match()` and `search()` filter
+/// functions. With `full` set the pattern must match the whole string.
+pub fn compile_iregexp(pattern: &str, full: bool) -> Result<Regex, ParseError> {
+    let translated = translate_iregexp(pattern);
+    let source = if full {
+        format!("^(?:{translated})$")
+    } else {
+        translated
+    };
+    Regex::new(&source).map_err(|e| ParseError::InvalidSyntax(format!("invalid regex: {e}")))
+}
+
+/// Compiles a `/pattern/flags` literal used with the `=~` operator.
+/// Supported flags: `i` (case-insensitive), `m` (multi-line), `s` (dot matches newline)
+/// and `x` (ignore whitespace).
+pub fn compile_with_flags(pattern: &str, flags: &str) -> Result<Regex, ParseError> {
+    let mut builder = RegexBuilder::new(pattern);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            _ => {
+                return Err(ParseError::InvalidSyntax(format!(
+                    "unknown regex flag `{flag}`"
+                )))
+            }
+        };
+    }
+    builder
+        .build()
+        .map_err(|e| ParseError::InvalidSyntax(format!("invalid regex: {e}")))
+}
+
+/// Rewrites I-Regexp syntax into `regex` syntax: outside a character class `.` matches any
+/// character except `\n` and `\r`, and a `[` inside a class is a literal.
+fn translate_iregexp(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                out.push(c);
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            }
+            '[' if in_class => out.push_str("\\["),
+            '[' => {
+                in_class = true;
+                out.push(c);
+            }
+            ']' if in_class => {
+                in_class = false;
+                out.push(c);
+            }
+            '.' if !in_class => out.push_str("[^\\n\\r]"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,49 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn spans_json() -> &'static str {
+    r#"{
+        "spans": [
+            { "id": "a", "start": 1700000000000000000, "end": 1700000000000000500, "count": 3, "limit": 5 },
+            { "id": "b", "start": 1700000000000000000, "end": 1700000000000002000, "count": 4, "limit": 8 },
+            { "id": "c", "start": 10, "end": 20, "count": 7, "limit": 20 }
+        ]
+    }"#
+}
+
+fn ids(path: &str) -> serde_json::Value {
+    jpl::from_json(spans_json(), path)
+}
+
+#[test]
+fn test_subtraction_keeps_integers_exact() {
+    // Nanosecond timestamps are beyond f64 precision; the difference must still be exact
+    assert_eq!(ids("$.spans[?(@.end - @.start > 1000)].id"), json!(["b"]));
+    assert_eq!(ids("$.spans[?(@.end - @.start == 500)].id"), json!(["a"]));
+}
+
+#[test]
+fn test_precedence_and_grouping() {
+    assert_eq!(
+        ids("$.spans[?(@.count * 2 >= @.limit)].id"),
+        json!(["a", "b"])
+    );
+    assert_eq!(ids("$.spans[?(@.count + 1 * 2 == 9)].id"), json!(["c"]));
+    assert_eq!(ids("$.spans[?((@.count + 1) * 2 == 16)].id"), json!(["c"]));
+    assert_eq!(
+        ids("$.spans[?(@.limit - @.count - 1 == 1)].id"),
+        json!(["a"])
+    );
+}
+
+#[test]
+fn test_remainder_division_and_unary_minus() {
+    assert_eq!(ids("$.spans[?(@.count % 2 == 0)].id"), json!(["b"]));
+    assert_eq!(ids("$.spans[?(@.count / 2 == 3.5)].id"), json!(["c"]));
+    assert_eq!(ids("$.spans[?(-@.count < -5)].id"), json!(["c"]));
+    // Division by zero yields no value, so the comparison fails
+    assert_eq!(
+        ids("$.spans[?(@.count / 0 == 0)].id"),
+        serde_json::Value::Null
+    );
+}
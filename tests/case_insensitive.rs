@@ -0,0 +1,80 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn users_json() -> &'static str {
+    r#"{
+        "users": [
+            { "name": "Alice", "city": "STRASSE" },
+            { "name": "ALICE", "city": "Straße" },
+            { "name": "bob", "city": "Berlin" }
+        ]
+    }"#
+}
+
+#[test]
+fn test_case_insensitive_operators() {
+    let result = jpl::from_json(users_json(), "$.users[?(@.name =~= 'alice')].name");
+    assert_eq!(result, json!(["Alice", "ALICE"]));
+    let result = jpl::from_json(users_json(), "$.users[?(@.name ieq 'BOB')].name");
+    assert_eq!(result, json!(["bob"]));
+    // Full case folding: ß folds to ss
+    let result = jpl::from_json(users_json(), "$.users[?(@.city ieq 'strasse')].name");
+    assert_eq!(result, json!(["Alice", "ALICE"]));
+    // The plain operators stay case-sensitive
+    let result = jpl::from_json(users_json(), "$.users[?(@.name == 'alice')].name");
+    assert!(result.is_null());
+}
+
+#[test]
+fn test_ignore_case_query_option() {
+    let opts = jpl::QueryOptions {
+        ignore_case: true,
+        ..Default::default()
+    };
+    let result =
+        jpl::from_json_with_options(users_json(), "$.users[?(@.name == 'alice')].name", opts);
+    assert_eq!(result, json!(["Alice", "ALICE"]));
+    let result = jpl::from_json_with_options(
+        users_json(),
+        "$.users[?(@.name in ['BOB', 'carol'])].name",
+        opts,
+    );
+    assert_eq!(result, json!(["bob"]));
+}
+
+#[test]
+fn test_ignore_case_from_expression() {
+    let expr = r#"from_json("{\"a\":[\"X\",\"y\"]}", "$.a[?(@ == 'x')]", "ignore_case")"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!(["X"]));
+    let expr = r#"from_json("{\"a\":[\"X\",\"y\"]}", "$.a[?@ == 'x']", "rfc9535,i")"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!(["X"]));
+}
+
+#[test]
+fn test_ignore_case_string_functions() {
+    let opts = jpl::QueryOptions {
+        ignore_case: true,
+        ..Default::default()
+    };
+    for (path, expected) in [
+        (
+            "$.users[?(contains(@.name, 'LIC'))].name",
+            json!(["Alice", "ALICE"]),
+        ),
+        (
+            "$.users[?(starts_with(@.name, 'ALI'))].name",
+            json!(["Alice", "ALICE"]),
+        ),
+        (
+            "$.users[?(ends_with(@.city, 'SSE'))].name",
+            json!(["Alice", "ALICE"]),
+        ),
+        ("$.users[?(starts_with(@.name, 'B'))].name", json!(["bob"])),
+    ] {
+        let result = jpl::from_json_with_options(users_json(), path, opts);
+        assert_eq!(result, expected, "{path}");
+    }
+    // Without the option the functions stay case-sensitive
+    let result = jpl::from_json(users_json(), "$.users[?(starts_with(@.name, 'ALI'))].name");
+    assert_eq!(result, json!(["ALICE"]));
+}
@@ -0,0 +1,60 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn values_json() -> &'static str {
+    r#"{"a":[{"v":3},{"v":"3"},{"v":"12"},{"v":"x"},{"v":true},{"v":null}]}"#
+}
+
+fn with_mode(path: &str, comparison: jpl::ComparisonMode) -> serde_json::Value {
+    let opts = jpl::QueryOptions {
+        comparison,
+        ..Default::default()
+    };
+    jpl::from_json_with_options(values_json(), path, opts)
+}
+
+#[test]
+fn test_lenient_comparison_is_the_default() {
+    // Numeric strings compare as numbers, other mixed types by their JSON text
+    let result = jpl::from_json(values_json(), "$.a[?(@.v == 3)].v");
+    assert_eq!(result, json!([3, "3"]));
+    let result = jpl::from_json(values_json(), "$.a[?(@.v > 'w')].v");
+    assert_eq!(result, json!([3, "x", true, null]));
+    assert_eq!(
+        with_mode("$.a[?(@.v == 3)].v", jpl::ComparisonMode::Lenient),
+        jpl::from_json(values_json(), "$.a[?(@.v == 3)].v")
+    );
+}
+
+#[test]
+fn test_strict_comparison() {
+    let strict = jpl::ComparisonMode::Strict;
+    assert_eq!(with_mode("$.a[?(@.v == 3)].v", strict), json!([3]));
+    // Cross-type ordering is always false
+    assert_eq!(with_mode("$.a[?(@.v > 'w')].v", strict), json!(["x"]));
+    assert_eq!(with_mode("$.a[?(@.v < 10)].v", strict), json!([3]));
+    assert_eq!(
+        with_mode("$.a[?(@.v in [3, null])].v", strict),
+        json!([3, null])
+    );
+}
+
+#[test]
+fn test_numeric_strings_comparison() {
+    let numeric = jpl::ComparisonMode::NumericStrings;
+    assert_eq!(with_mode("$.a[?(@.v == 3)].v", numeric), json!([3, "3"]));
+    assert_eq!(with_mode("$.a[?(@.v > 5)].v", numeric), json!(["12"]));
+    // Non-numeric strings and other types are still not coerced
+    assert_eq!(with_mode("$.a[?(@.v > 'w')].v", numeric), json!(["x"]));
+}
+
+#[test]
+fn test_comparison_mode_from_evaluator() {
+    let expr = r#"from_json("{\"a\":[1,\"1\"]}", "$.a[?(@ == 1)]")"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!([1, "1"]));
+    let ev = jpl::Evaluator::new().with_comparison_mode(jpl::ComparisonMode::Strict);
+    assert_eq!(ev.eval(expr).unwrap(), json!([1]));
+    // The options argument of from_json overrides the evaluator
+    let expr = r#"from_json("{\"a\":[1,\"1\"]}", "$.a[?(@ == 1)]", "lenient")"#;
+    assert_eq!(ev.eval(expr).unwrap(), json!([1, "1"]));
+}
@@ -0,0 +1,91 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::Value;
+use std::fs;
+
+// Runs the cases in tests/cts/cts.json through the RFC 9535 mode of the JSONPath engine.
+// The file is not yet the upstream Compliance Test Suite, see tests/cts/README.md. Every case is reported as PASS, FAIL or SKIP;
+// run with `cargo test --test compliance -- --nocapture` to see the report.
+
+/// Cases that are known to diverge from RFC 9535, by CTS name.
+/// A listed case that starts passing fails the run, so that this list only shrinks.
+const KNOWN_DIVERGENCES: &[&str] = &[];
+
+/// Runs a single case and describes the mismatch, if any.
+fn run_case(case: &Value) -> Result<(), String> {
+    let selector = case["selector"].as_str().ok_or("case without selector")?;
+    let document = case.get("document").cloned().unwrap_or(Value::Null);
+    // In RFC 9535 mode a nodelist is always an array and Null means the selector was rejected
+    let actual = jpl::engine::from_value_with_mode(&document, selector, jpl::PathMode::Rfc9535);
+
+    if case["invalid_selector"].as_bool() == Some(true) {
+        return if actual.is_null() {
+            Ok(())
+        } else {
+            Err(format!("expected an invalid selector, got {actual}"))
+        };
+    }
+    if actual.is_null() {
+        return Err("selector was rejected as invalid".into());
+    }
+    if let Some(expected) = case.get("result") {
+        if &actual != expected {
+            return Err(format!("expected {expected}, got {actual}"));
+        }
+    } else if let Some(results) = case.get("results").and_then(Value::as_array) {
+        if !results.contains(&actual) {
+            return Err(format!("expected one of {results:?}, got {actual}"));
+        }
+    } else {
+        return Err("case without expected result".into());
+    }
+    Ok(())
+}
+
+#[test]
+fn compliance_test_suite() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cts/cts.json");
+    let suite: Value = serde_json::from_str(&fs::read_to_string(path).expect("read cts.json"))
+        .expect("parse cts.json");
+    let cases = suite["tests"]
+        .as_array()
+        .expect("cts.json has a tests array");
+
+    let (mut passed, mut skipped) = (0, 0);
+    let mut failures = Vec::new();
+    for case in cases {
+        let name = case["name"].as_str().unwrap_or("<unnamed>");
+        let known = KNOWN_DIVERGENCES.contains(&name);
+        match (run_case(case), known) {
+            (Ok(()), false) => {
+                passed += 1;
+                println!("PASS {name}");
+            }
+            (Err(e), true) => {
+                skipped += 1;
+                println!("SKIP {name}: {e}");
+            }
+            (Err(e), false) => {
+                println!("FAIL {name}: {e}");
+                failures.push(name.to_string());
+            }
+            (Ok(()), true) => {
+                println!("FAIL {name}: passes but is listed in KNOWN_DIVERGENCES");
+                failures.push(name.to_string());
+            }
+        }
+    }
+
+    // Allowlist entries must refer to cases that exist
+    for name in KNOWN_DIVERGENCES {
+        if !cases.iter().any(|c| c["name"].as_str() == Some(name)) {
+            println!("FAIL {name}: listed in KNOWN_DIVERGENCES but not in cts.json");
+            failures.push(name.to_string());
+        }
+    }
+
+    println!(
+        "compliance: {passed} passed, {} failed, {skipped} skipped",
+        failures.len()
+    );
+    assert!(failures.is_empty(), "compliance failures: {failures:#?}");
+}
@@ -0,0 +1,65 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn services_json() -> &'static str {
+    r#"{
+        "services": [
+            { "name": "api", "labels": { "tier": "web", "replicas": 2 }, "coords": [1, 2] },
+            { "name": "db", "labels": { "replicas": 2.0, "tier": "data" }, "coords": [2, 1] },
+            { "name": "cache", "labels": { "tier": "data" }, "coords": [1, 2, 3] }
+        ]
+    }"#
+}
+
+#[test]
+fn test_object_equality_ignores_member_order() {
+    let result = jpl::from_json(
+        services_json(),
+        r#"$.services[?(@.labels == {"replicas": 2, 'tier': 'web'})].name"#,
+    );
+    assert_eq!(result, json!(["api"]));
+    // Numbers compare by value, not by formatting
+    let result = jpl::from_json(
+        services_json(),
+        r#"$.services[?(@.labels == {"tier": "data", "replicas": 2})].name"#,
+    );
+    assert_eq!(result, json!(["db"]));
+    let result = jpl::from_json(
+        services_json(),
+        r#"$.services[?(@.labels != {"tier": "data"})].name"#,
+    );
+    assert_eq!(result, json!(["api", "db"]));
+}
+
+#[test]
+fn test_array_equality_is_ordered() {
+    let result = jpl::from_json(services_json(), "$.services[?(@.coords == [1, 2])].name");
+    assert_eq!(result, json!(["api"]));
+    let result = jpl::from_json(
+        services_json(),
+        "$.services[?(@.coords == [1.0, 2, 3])].name",
+    );
+    assert_eq!(result, json!(["cache"]));
+}
+
+#[test]
+fn test_structured_literals() {
+    // Nested literals, and membership over object literals
+    let result = jpl::from_json(
+        services_json(),
+        r#"$.services[?(@.labels in [{"tier": "data"}, {"a": {"b": []}}])].name"#,
+    );
+    assert_eq!(result, json!(["cache"]));
+    // Elements must be literals, and RFC 9535 has no structured literals
+    assert!(jpl::from_json(
+        services_json(),
+        r#"$.services[?(@.labels == {"a": @.name})]"#
+    )
+    .is_null());
+    let rfc = jpl::from_json_with_mode(
+        services_json(),
+        r#"$.services[?@.labels == {"tier": "data"}]"#,
+        jpl::PathMode::Rfc9535,
+    );
+    assert!(rfc.is_null());
+}
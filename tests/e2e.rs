@@ -35,3 +35,17 @@ fn test_example_slice() {
     let out = jpl::eval(expr).unwrap();
     assert_eq!(out, json!([1, 3]));
 }
+
+#[test]
+fn test_slice_with_huge_step() {
+    // Stepping past the i64 range ends the slice instead of wrapping around
+    let json = r#"{"a":[1,2,3,4,5]}"#;
+    assert_eq!(
+        jpl::from_json(json, "$.a[1:5:9223372036854775807]"),
+        json!([2])
+    );
+    assert_eq!(
+        jpl::from_json(json, "$.a[0:4:-9223372036854775807]"),
+        json!([4])
+    );
+}
@@ -0,0 +1,33 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+#[test]
+fn test_scalar_literals() {
+    assert_eq!(jpl::eval("42").unwrap(), json!(42));
+    assert_eq!(jpl::eval("-1.5e2").unwrap().as_f64(), Some(-150.0));
+    assert_eq!(jpl::eval("true").unwrap(), json!(true));
+    assert_eq!(jpl::eval(" null ").unwrap(), json!(null));
+}
+
+#[test]
+fn test_structured_literals_with_nested_expressions() {
+    let expr = r#"{"name": first(from_json("{\"a\":[\"x\",\"y\"]}", "$.a")), "tags": [1, false, unique([2, 2])], "none": {}}"#;
+    assert_eq!(
+        jpl::eval(expr).unwrap(),
+        json!({"name": "x", "tags": [1, false, [2]], "none": {}})
+    );
+    // Keys must be strings, and literals must be complete
+    assert!(jpl::eval("{a: 1}").is_err());
+    assert!(jpl::eval("[1, 2").is_err());
+}
+
+#[test]
+fn test_or_default_with_literal_default() {
+    let expr = r#"or_default(from_json("{\"a\":1}", "$.missing"), {"fallback": true})"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!({"fallback": true}));
+    let expr = r#"or_default(from_json("{\"a\":1}", "$.missing"), 5)"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!(5));
+    // A string default is still parsed as JSON
+    let expr = r#"or_default(from_json("{\"a\":1}", "$.missing"), "[1]")"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!([1]));
+}
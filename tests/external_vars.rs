@@ -0,0 +1,64 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::process::Command;
+
+fn vars() -> HashMap<String, Value> {
+    HashMap::from([
+        ("input".to_string(), json!({"a": [1, 2]})),
+        ("raw".to_string(), json!(r#"{"b":[3,3]}"#)),
+    ])
+}
+
+#[test]
+fn test_eval_with_variables() {
+    let ev = jpl::Evaluator::new();
+    assert_eq!(
+        ev.eval_with("[$input, $raw]", &vars()).unwrap(),
+        json!([{"a": [1, 2]}, r#"{"b":[3,3]}"#])
+    );
+    // A variable that is not passed in is an error
+    assert!(ev
+        .eval_with("[$input, $missing | or_default(0)]", &vars())
+        .is_err());
+    let expr = r#"first(query($input, "$.a[*]"))"#;
+    assert_eq!(ev.eval_with(expr, &vars()).unwrap(), json!(1));
+    let expr = r#"from_json($raw, "$.b[*]") | unique()"#;
+    assert_eq!(ev.eval_with(expr, &vars()).unwrap(), json!([3]));
+    // A let binding shadows an external variable
+    let expr = "let input = 5; $input";
+    assert_eq!(ev.eval_with(expr, &vars()).unwrap(), json!(5));
+}
+
+#[test]
+fn test_cli_var_and_var_file() {
+    let file = std::env::temp_dir().join(format!("jpl-var-{}.json", std::process::id()));
+    std::fs::write(&file, r#"{"a": [1, 2]}"#).unwrap();
+    let out = Command::new(env!("CARGO_BIN_EXE_jpl"))
+        .arg("--var")
+        .arg("n=[3]")
+        .arg("--var-file")
+        .arg(format!("doc={}", file.display()))
+        .arg("--expr")
+        .arg("[$doc, first($n)]")
+        .output()
+        .unwrap();
+    std::fs::remove_file(&file).unwrap();
+    assert!(out.status.success());
+    let value: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(value, json!([{"a": [1, 2]}, 3]));
+
+    // Invalid JSON is rejected before evaluation
+    let out = Command::new(env!("CARGO_BIN_EXE_jpl"))
+        .args(["--var", "n={", "--expr", "$n"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+
+    // So is a misspelled variable
+    let out = Command::new(env!("CARGO_BIN_EXE_jpl"))
+        .args(["--var", "input=1", "--expr", "$inptu"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+}
@@ -0,0 +1,165 @@
+use jpl::{FilterFunction, FunctionType, FunctionValue, PathMode, QueryOptions};
+use json_path_like_value_retrieval as jpl;
+use serde_json::{json, Value};
+
+/// `attr(attributes, key)`: looks up the value of an OTEL attribute by key.
+struct Attr;
+
+impl FilterFunction for Attr {
+    fn name(&self) -> &str {
+        "attr"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value, FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        let (FunctionValue::Value(Some(Value::Array(attrs))), FunctionValue::Value(Some(key))) =
+            (&args[0], &args[1])
+        else {
+            return FunctionValue::Value(None);
+        };
+        let found = attrs.iter().find(|a| a.get("key") == Some(key));
+        FunctionValue::Value(found.and_then(|a| a.get("value")).cloned())
+    }
+}
+
+/// `either(a, b)`: logical OR of two logical arguments.
+struct Either;
+
+impl FilterFunction for Either {
+    fn name(&self) -> &str {
+        "either"
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Logical, FunctionType::Logical]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Logical
+    }
+
+    fn call(&self, args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        FunctionValue::Logical(args.contains(&FunctionValue::Logical(true)))
+    }
+}
+
+fn spans_json() -> &'static str {
+    r#"{"spans":[
+        {"id":"1","attributes":[{"key":"service.name","value":"api"}]},
+        {"id":"2","attributes":[{"key":"service.name","value":"db"}]},
+        {"id":"3","attributes":[]}
+    ]}"#
+}
+
+#[test]
+fn test_registered_value_function() {
+    jpl::register_function(Attr).unwrap();
+    let result = jpl::from_json(
+        spans_json(),
+        "$.spans[?(attr(@.attributes, 'service.name') == 'db')].id",
+    );
+    assert_eq!(result, json!(["2"]));
+    // User functions are function extensions in RFC 9535 mode as well
+    let result = jpl::from_json_with_mode(
+        spans_json(),
+        "$.spans[?attr(@.attributes, 'service.name') == 'api'].id",
+        PathMode::Rfc9535,
+    );
+    assert_eq!(result, json!(["1"]));
+}
+
+#[test]
+fn test_registered_logical_function() {
+    jpl::register_function(Either).unwrap();
+    let result = jpl::from_json(
+        spans_json(),
+        "$.spans[?(either(@.id == '1', @.id == '3'))].id",
+    );
+    assert_eq!(result, json!(["1", "3"]));
+}
+
+#[test]
+fn test_function_arguments_are_type_checked() {
+    jpl::register_function(Attr).unwrap();
+    jpl::register_function(Either).unwrap();
+    for path in [
+        // Wrong number of arguments
+        "$.spans[?(attr(@.attributes) == 'db')]",
+        "$.spans[?(length(@.id, 1) == 1)]",
+        // Unknown function
+        "$.spans[?(nope(@.id))]",
+    ] {
+        assert!(jpl::from_json(spans_json(), path).is_null(), "{path}");
+    }
+    // A logical result cannot be compared, and a value result is not a test in RFC 9535
+    for path in [
+        "$.spans[?either(@.id, @.x) == true]",
+        "$.spans[?attr(@.attributes, 'service.name')]",
+    ] {
+        let result = jpl::from_json_with_mode(spans_json(), path, PathMode::Rfc9535);
+        assert!(result.is_null(), "{path}");
+    }
+}
+
+#[test]
+fn test_builtin_functions_use_the_registry() {
+    let json = r#"{"a":[{"n":"Alice"},{"n":"BOB"},{"n":"carol"}]}"#;
+    assert_eq!(
+        jpl::from_json(json, "$.a[?(lower(@.n) == 'bob')].n"),
+        json!(["BOB"])
+    );
+    assert_eq!(
+        jpl::from_json(json, "$.a[?(upper(@.n) == 'CAROL')].n"),
+        json!(["carol"])
+    );
+    assert_eq!(
+        jpl::from_json(json, "$.a[?(length(@.n) == 5)].n"),
+        json!(["Alice", "carol"])
+    );
+}
+
+/// A function that always returns true, registered under an arbitrary name.
+struct Always(&'static str);
+
+impl FilterFunction for Always {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn params(&self) -> &[FunctionType] {
+        &[FunctionType::Value]
+    }
+
+    fn result(&self) -> FunctionType {
+        FunctionType::Value
+    }
+
+    fn call(&self, _args: &[FunctionValue], _opts: QueryOptions) -> FunctionValue {
+        FunctionValue::Value(Some(json!(true)))
+    }
+}
+
+#[test]
+fn test_reserved_and_standard_names_are_rejected() {
+    // Reserved by the filter syntax or standard
+    for name in ["match", "search", "any", "all", "length", "count", "value"] {
+        assert!(jpl::register_function(Always(name)).is_err(), "{name}");
+    }
+    // Not callable from a filter
+    for name in ["Attr", "", "service-name", "_x", "1x", "naïve"] {
+        assert!(jpl::register_function(Always(name)).is_err(), "{name}");
+    }
+    jpl::register_function(Always("always_2")).unwrap();
+    let result = jpl::from_json(spans_json(), "$.spans[?(always_2(@.id) == true)].id");
+    assert_eq!(result, json!(["1", "2", "3"]));
+    // The standard functions are left in place
+    let result = jpl::from_json(spans_json(), "$.spans[?(length(@.attributes) == 1)].id");
+    assert_eq!(result, json!(["1", "2"]));
+}
@@ -0,0 +1,46 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn spans_json() -> &'static str {
+    r#"{
+        "spans": [
+            {
+                "name": "a",
+                "items": [1, 2, 3, 4],
+                "attributes": [{ "key": "x", "value": 1 }, { "key": "y", "value": 2 }],
+                "meta": { "inner": { "name": "deep" } }
+            },
+            {
+                "name": "b",
+                "items": [5],
+                "attributes": [{ "key": "y", "value": 3 }],
+                "meta": {}
+            }
+        ]
+    }"#
+}
+
+#[test]
+fn test_slice_in_filter_path() {
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.items[1:3])].name");
+    assert_eq!(result, json!(["a"]));
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.items[-1:] == 5)].name");
+    assert_eq!(result, json!(["b"]));
+}
+
+#[test]
+fn test_recursive_descent_in_filter_path() {
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.meta..name == 'deep')].name");
+    assert_eq!(result, json!(["a"]));
+}
+
+#[test]
+fn test_nested_filter_in_filter_path() {
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.attributes[?(@.key=='x')])].name");
+    assert_eq!(result, json!(["a"]));
+    let result = jpl::from_json(
+        spans_json(),
+        "$.spans[?(@.attributes[?(@.key=='y')].value == 3)].name",
+    );
+    assert_eq!(result, json!(["b"]));
+}
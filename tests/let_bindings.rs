@@ -0,0 +1,38 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+#[test]
+fn test_let_binding_is_reused() {
+    let expr = r#"let doc = "{\"a\":[1,2],\"b\":[3]}";
+        [first(from_json($doc, "$.a[*]")), first(from_json($doc, "$.b[*]"))]"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!([1, 3]));
+}
+
+#[test]
+fn test_bindings_chain_and_shadow() {
+    let expr = r#"let names = from_json("{\"u\":[{\"n\":\"a\"},{\"n\":\"a\"}]}", "$.u[*].n");
+        let names = $names | unique();
+        {"names": $names, "first": first($names)}"#;
+    assert_eq!(
+        jpl::eval(expr).unwrap(),
+        json!({"names": ["a"], "first": "a"})
+    );
+}
+
+#[test]
+fn test_unbound_and_malformed_bindings() {
+    // An unbound variable is an error, even where its value would not matter
+    for expr in [
+        "$missing",
+        "let x = 1; $y | or_default(2)",
+        "let x = $x; $x",
+        "let x = 1; let y = $y; [$x, $y]",
+    ] {
+        assert!(
+            matches!(jpl::eval(expr), Err(jpl::errors::EvalError::Runtime(_))),
+            "{expr}"
+        );
+    }
+    assert!(jpl::eval("let x = 1 $x").is_err());
+    assert!(jpl::eval("let x = 1;").is_err());
+}
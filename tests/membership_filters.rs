@@ -0,0 +1,68 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn deployments_json() -> &'static str {
+    r#"{
+        "deployments": [
+            { "name": "a", "env": "prod", "tags": ["web", "eu"], "image": "api:1.2" },
+            { "name": "b", "env": "staging", "tags": ["web"], "image": "worker:2.0" },
+            { "name": "c", "env": "dev", "tags": ["batch", "us"], "image": "api:1.3" },
+            { "name": "d", "env": "prod", "tags": [], "image": "cron:0.1" }
+        ]
+    }"#
+}
+
+fn names(path: &str) -> serde_json::Value {
+    jpl::from_json(deployments_json(), path)
+}
+
+#[test]
+fn test_in_and_nin() {
+    assert_eq!(
+        names("$.deployments[?(@.env in ['prod', 'staging'])].name"),
+        json!(["a", "b", "d"])
+    );
+    assert_eq!(
+        names("$.deployments[?(@.env nin ['prod', 'staging'])].name"),
+        json!(["c"])
+    );
+}
+
+#[test]
+fn test_contains() {
+    assert_eq!(
+        names("$.deployments[?(@.tags contains 'web')].name"),
+        json!(["a", "b"])
+    );
+    // Substring containment on strings
+    assert_eq!(
+        names("$.deployments[?(@.image contains 'api:')].name"),
+        json!(["a", "c"])
+    );
+}
+
+#[test]
+fn test_subsetof_anyof_noneof() {
+    assert_eq!(
+        names("$.deployments[?(@.tags subsetof ['web', 'eu', 'us'])].name"),
+        json!(["a", "b", "d"])
+    );
+    assert_eq!(
+        names("$.deployments[?(@.tags anyof ['eu', 'us'])].name"),
+        json!(["a", "c"])
+    );
+    assert_eq!(
+        names("$.deployments[?(@.tags noneof ['eu', 'us'])].name"),
+        json!(["b", "d"])
+    );
+}
+
+#[test]
+fn test_membership_operators_are_not_rfc9535() {
+    let result = jpl::from_json_with_mode(
+        deployments_json(),
+        "$.deployments[?@.env in ['prod']]",
+        jpl::PathMode::Rfc9535,
+    );
+    assert!(result.is_null());
+}
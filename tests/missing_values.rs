@@ -0,0 +1,43 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn spans_json() -> &'static str {
+    r#"{
+        "spans": [
+            { "id": "a", "parentId": null },
+            { "id": "b", "parentId": "a" },
+            { "id": "c" }
+        ]
+    }"#
+}
+
+#[test]
+fn test_missing_is_not_null() {
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.parentId == null)].id");
+    assert_eq!(result, json!(["a"]));
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.parentId != null)].id");
+    assert_eq!(result, json!(["b", "c"]));
+    // Two missing values are equal, and Nothing is not ordered
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.parentId == @.missing)].id");
+    assert_eq!(result, json!(["c"]));
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.parentId <= 'z')].id");
+    assert_eq!(result, json!(["b"]));
+}
+
+#[test]
+fn test_missing_in_membership() {
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.parentId in [null, 'x'])].id");
+    assert_eq!(result, json!(["a"]));
+    let result = jpl::from_json(spans_json(), "$.spans[?(@.parentId nin ['a'])].id");
+    assert_eq!(result, json!(["a", "c"]));
+}
+
+#[test]
+fn test_exists_and_is_null() {
+    let result = jpl::from_json(spans_json(), "$.spans[?(exists(@.parentId))].id");
+    assert_eq!(result, json!(["a", "b"]));
+    let result = jpl::from_json(spans_json(), "$.spans[?(!exists(@.parentId))].id");
+    assert_eq!(result, json!(["c"]));
+    let result = jpl::from_json(spans_json(), "$.spans[?(is_null(@.parentId))].id");
+    assert_eq!(result, json!(["a"]));
+}
@@ -0,0 +1,58 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn services_json() -> &'static str {
+    r#"{
+        "services": [
+            { "name": "api", "tags": ["dev", "prod"] },
+            { "name": "db", "tags": ["prod"] },
+            { "name": "cache", "tags": [] },
+            { "name": "queue", "tags": ["dev", false] }
+        ]
+    }"#
+}
+
+#[test]
+fn test_multi_node_existence() {
+    let result = jpl::from_json(services_json(), "$.services[?(@.tags[*])].name");
+    assert_eq!(result, json!(["api", "db", "queue"]));
+    let result = jpl::from_json_with_mode(
+        services_json(),
+        "$.services[?@.tags[*]].name",
+        jpl::PathMode::Rfc9535,
+    );
+    assert_eq!(result, json!(["api", "db", "queue"]));
+}
+
+#[test]
+fn test_multi_node_comparison_defaults_to_any() {
+    let result = jpl::from_json(services_json(), "$.services[?(@.tags[*] == 'prod')].name");
+    assert_eq!(result, json!(["api", "db"]));
+    let result = jpl::from_json(
+        services_json(),
+        "$.services[?(any(@.tags[*]) == 'prod')].name",
+    );
+    assert_eq!(result, json!(["api", "db"]));
+}
+
+#[test]
+fn test_all_quantifier() {
+    let result = jpl::from_json(
+        services_json(),
+        "$.services[?(all(@.tags[*]) == 'prod')].name",
+    );
+    // An empty node list never satisfies `all`
+    assert_eq!(result, json!(["db"]));
+    let result = jpl::from_json(services_json(), "$.services[?(all(@.tags[*]))].name");
+    assert_eq!(result, json!(["api", "db"]));
+}
+
+#[test]
+fn test_otel_attribute_lookup() {
+    let json = r#"{"spans":[
+        {"id":"1","attributes":[{"key":"env","value":"prod"},{"key":"region","value":"eu"}]},
+        {"id":"2","attributes":[{"key":"env","value":"dev"}]}
+    ]}"#;
+    let result = jpl::from_json(json, "$.spans[?(@.attributes[*].value == 'eu')].id");
+    assert_eq!(result, json!(["1"]));
+}
@@ -0,0 +1,33 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn events_json() -> &'static str {
+    r#"{
+        "events": [
+            { "name": "start", "attributes": [{ "key": "k", "value": "first" }] },
+            { "name": "end", "attributes": [{ "key": "k", "value": "a" }, { "key": "k", "value": "last" }] }
+        ]
+    }"#
+}
+
+#[test]
+fn test_negative_index_in_path() {
+    assert_eq!(
+        jpl::from_json(events_json(), "$.events[-1].name"),
+        json!(["end"])
+    );
+    assert_eq!(
+        jpl::from_json(events_json(), "$.events[-2].name"),
+        json!(["start"])
+    );
+    assert!(jpl::from_json(events_json(), "$.events[-3]").is_null());
+}
+
+#[test]
+fn test_negative_index_in_filter_path() {
+    let result = jpl::from_json(
+        events_json(),
+        "$.events[?(@.attributes[-1].value == 'last')].name",
+    );
+    assert_eq!(result, json!(["end"]));
+}
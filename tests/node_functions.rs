@@ -0,0 +1,54 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn tree_json() -> &'static str {
+    r#"{
+        "nodes": [
+            { "name": "a", "children": [1, 2, 3], "meta": { "id": 5 } },
+            { "name": "b", "children": [1], "meta": { "id": 5, "inner": { "id": 6 } } },
+            { "name": "c", "children": [], "meta": {} }
+        ]
+    }"#
+}
+
+#[test]
+fn test_count() {
+    let result = jpl::from_json(tree_json(), "$.nodes[?(count(@.children[*]) > 2)].name");
+    assert_eq!(result, json!(["a"]));
+    let result = jpl::from_json_with_mode(
+        tree_json(),
+        "$.nodes[?count(@.children[*]) == 0].name",
+        jpl::PathMode::Rfc9535,
+    );
+    assert_eq!(result, json!(["c"]));
+}
+
+#[test]
+fn test_value() {
+    // value() of several nodes or of no node is Nothing, which equals nothing
+    let result = jpl::from_json_with_mode(
+        tree_json(),
+        "$.nodes[?value(@..id) == 5].name",
+        jpl::PathMode::Rfc9535,
+    );
+    assert_eq!(result, json!(["a"]));
+    let result = jpl::from_json(tree_json(), "$.nodes[?(value(@..id) == 5)].name");
+    assert_eq!(result, json!(["a"]));
+}
+
+#[test]
+fn test_node_functions_are_type_checked() {
+    for path in [
+        // count() and value() take a query, not a value
+        "$.nodes[?count(1) == 1]",
+        "$.nodes[?value('x') == 1]",
+        // A value result is not a test expression
+        "$.nodes[?count(@.children[*])]",
+        // length() takes a value, not a node list
+        "$.nodes[?length(@.children[*]) == 1]",
+    ] {
+        let result = jpl::from_json_with_mode(tree_json(), path, jpl::PathMode::Rfc9535);
+        assert!(result.is_null(), "{path} should be rejected");
+    }
+    assert!(jpl::from_json(tree_json(), "$.nodes[?(count('x') > 0)]").is_null());
+}
@@ -0,0 +1,66 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn spans_json() -> &'static str {
+    r#"{"spans":[
+        {"id":"a","timeUnixNano":1700000000000000001},
+        {"id":"b","timeUnixNano":1700000000000000002},
+        {"id":"c","timeUnixNano":18446744073709551615}
+    ]}"#
+}
+
+#[test]
+fn test_large_integers_compare_exactly() {
+    let result = jpl::from_json(
+        spans_json(),
+        "$.spans[?(@.timeUnixNano == 1700000000000000001)].id",
+    );
+    assert_eq!(result, json!(["a"]));
+    let result = jpl::from_json(
+        spans_json(),
+        "$.spans[?(@.timeUnixNano > 1700000000000000001)].id",
+    );
+    assert_eq!(result, json!(["b", "c"]));
+    // Beyond 2^53, neighbouring integers are distinct
+    let json = r#"{"a":[9007199254740992, 9007199254740993]}"#;
+    assert_eq!(
+        jpl::from_json(json, "$.a[?(@ == 9007199254740993)]"),
+        json!([9007199254740993u64])
+    );
+    let rfc = jpl::from_json_with_mode(json, "$.a[?@ < 9007199254740993]", jpl::PathMode::Rfc9535);
+    assert_eq!(rfc, json!([9007199254740992u64]));
+}
+
+#[test]
+fn test_integers_and_floats_compare_by_value() {
+    let json = r#"{"a":[1, 1.0, 1.5, 9007199254740993]}"#;
+    assert_eq!(jpl::from_json(json, "$.a[?(@ == 1)]"), json!([1, 1.0]));
+    assert_eq!(
+        jpl::from_json(json, "$.a[?(@ > 1.25)]"),
+        json!([1.5, 9007199254740993u64])
+    );
+    // The float 9007199254740992.0 is not rounded up to meet the integer
+    assert_eq!(
+        jpl::from_json(json, "$.a[?(@ > 9007199254740992.0)]"),
+        json!([9007199254740993u64])
+    );
+    // Numeric strings are compared exactly as well
+    let json = r#"{"a":["9007199254740993", "0.5"]}"#;
+    assert_eq!(
+        jpl::from_json(json, "$.a[?(@ > 9007199254740992)]"),
+        json!(["9007199254740993"])
+    );
+}
+
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn test_arbitrary_precision_decimals() {
+    let json = r#"{"a":[0.1, 0.10000000000000000001, 123456789012345678901234567890]}"#;
+    let result = jpl::from_json(json, "$.a[?(@ > 0.1)]");
+    assert_eq!(result.as_array().map(Vec::len), Some(2));
+    let result = jpl::from_json(json, "$.a[?(@ == 123456789012345678901234567890)]");
+    assert_eq!(result.as_array().map(Vec::len), Some(1));
+    let path = "$.a[?@ < 1.23456789012345678901234567891e29]";
+    let result = jpl::from_json_with_mode(json, path, jpl::PathMode::Rfc9535);
+    assert_eq!(result.as_array().map(Vec::len), Some(3));
+}
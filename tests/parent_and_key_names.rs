@@ -0,0 +1,50 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn resource_json() -> &'static str {
+    r#"{
+        "resource": {
+            "service.name": "checkout",
+            "host.name": "web-1"
+        },
+        "spans": [
+            { "id": "a", "attributes": [{ "key": "db", "value": "x" }] },
+            { "id": "b", "attributes": [{ "key": "http", "value": "y" }] }
+        ]
+    }"#
+}
+
+#[test]
+fn test_parent_operator() {
+    // The attribute object that holds the matching value, then the span above it
+    let result = jpl::from_json(resource_json(), "$..value[?(@ == 'x')]^");
+    assert_eq!(result, json!([{ "key": "db", "value": "x" }]));
+    let result = jpl::from_json(resource_json(), "$..value[?(@ == 'y')]^^^.id");
+    assert_eq!(result, json!(["b"]));
+    // The root has no parent
+    assert!(jpl::from_json(resource_json(), "$^").is_null());
+}
+
+#[test]
+fn test_key_name_operator() {
+    let result = jpl::from_json(resource_json(), "$.resource.*~");
+    assert_eq!(result, json!(["host.name", "service.name"]));
+    // Array elements are named by their index
+    let result = jpl::from_json(resource_json(), "$.spans[?(@.id == 'b')]~");
+    assert_eq!(result, json!([1]));
+    let result = jpl::from_json(resource_json(), "$..value[?(@ == 'y')]^^^~");
+    assert_eq!(result, json!([1]));
+}
+
+#[test]
+fn test_operators_in_filters_and_rfc_mode() {
+    let result = jpl::from_json(
+        resource_json(),
+        "$.resource[?(@.*~ == 'service.name')]['host.name']",
+    );
+    assert_eq!(result, json!(["web-1"]));
+    // Nothing can follow `~`, and RFC 9535 has neither operator
+    assert!(jpl::from_json(resource_json(), "$.resource.*~.x").is_null());
+    let rfc = jpl::from_json_with_mode(resource_json(), "$.resource.*~", jpl::PathMode::Rfc9535);
+    assert!(rfc.is_null());
+}
@@ -0,0 +1,31 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+const DOC: &str = r#""{\"users\":[{\"name\":\"b\"},{\"name\":\"a\"},{\"name\":\"b\"}]}""#;
+
+#[test]
+fn test_pipeline_matches_nested_calls() {
+    let piped = jpl::eval(&format!(
+        r#"from_json({DOC}, "$..name") | unique() | first()"#
+    ))
+    .unwrap();
+    let nested = jpl::eval(&format!(r#"first(unique(from_json({DOC}, "$..name")))"#)).unwrap();
+    assert_eq!(piped, json!("b"));
+    assert_eq!(piped, nested);
+}
+
+#[test]
+fn test_pipeline_passes_first_argument() {
+    let expr = format!(r#"{DOC} | from_json("$.missing") | or_default("none")"#);
+    assert_eq!(jpl::eval(&expr).unwrap(), json!("none"));
+    // Pipelines nest inside arguments and literals
+    let expr = format!(r#"[from_json({DOC}, "$..name") | unique(), null | or_default(0)]"#);
+    assert_eq!(jpl::eval(&expr).unwrap(), json!([["b", "a"], 0]));
+}
+
+#[test]
+fn test_pipeline_requires_a_call() {
+    assert!(jpl::eval(r#""x" | "y""#).is_err());
+    assert!(jpl::eval(r#"[1] | first"#).is_err());
+    assert!(jpl::eval(r#"[1] |"#).is_err());
+}
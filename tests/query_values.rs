@@ -0,0 +1,37 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn trace() -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": [{ "key": "service.name", "value": "checkout" }] },
+            "spans": [{ "name": "GET" }, { "name": "POST" }, { "name": "GET" }]
+        }]
+    })
+}
+
+#[test]
+fn test_engine_query() {
+    assert_eq!(
+        jpl::query(&trace(), "$..spans[*].name"),
+        json!(["GET", "POST", "GET"])
+    );
+    assert!(jpl::query(&trace(), "$.missing").is_null());
+    assert!(jpl::query(&trace(), "$[").is_null());
+}
+
+#[test]
+fn test_query_builtin_chains_lookups() {
+    let vars = HashMap::from([("input".to_string(), trace())]);
+    let ev = jpl::Evaluator::new();
+    let expr =
+        r#"query($input, "$.resourceSpans[0]") | first() | query("$.spans[*].name") | unique()"#;
+    assert_eq!(ev.eval_with(expr, &vars).unwrap(), json!(["GET", "POST"]));
+    let expr =
+        r#"first(query($input, "$..attributes[?(@.key == 'SERVICE.NAME')].value", "ignore_case"))"#;
+    assert_eq!(ev.eval_with(expr, &vars).unwrap(), json!("checkout"));
+    // Literals can be queried too
+    let expr = r#"query({"a": [1, 2]}, "$.a[-1]", "rfc9535")"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!([2]));
+}
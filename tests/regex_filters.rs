@@ -0,0 +1,56 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn services_json() -> &'static str {
+    r#"{
+        "pattern": "^db-",
+        "services": [
+            { "name": "svc-api" },
+            { "name": "SVC-Auth" },
+            { "name": "db-main" },
+            { "name": "legacy-svc-x" },
+            { "name": 42 }
+        ]
+    }"#
+}
+
+#[test]
+fn test_regex_operator_with_flags() {
+    let result = jpl::from_json(services_json(), "$.services[?(@.name =~ /^svc-.*/)].name");
+    assert_eq!(result, json!(["svc-api"]));
+    let result = jpl::from_json(services_json(), "$.services[?(@.name =~ /^svc-.*/i)].name");
+    assert_eq!(result, json!(["svc-api", "SVC-Auth"]));
+    // Without anchors the operator finds a match anywhere in the string
+    let result = jpl::from_json(services_json(), "$.services[?(@.name =~ /svc/)].name");
+    assert_eq!(result, json!(["svc-api", "legacy-svc-x"]));
+}
+
+#[test]
+fn test_match_and_search_functions() {
+    // match() must cover the whole string, search() finds a substring
+    let result = jpl::from_json(
+        services_json(),
+        "$.services[?(match(@.name, 'svc-.*'))].name",
+    );
+    assert_eq!(result, json!(["svc-api"]));
+    let result = jpl::from_json(services_json(), "$.services[?(search(@.name, 'svc'))].name");
+    assert_eq!(result, json!(["svc-api", "legacy-svc-x"]));
+    let result = jpl::from_json(
+        services_json(),
+        "$.services[?(search(@.name, $.pattern))].name",
+    );
+    assert_eq!(result, json!(["db-main"]));
+    let result = jpl::from_json_with_mode(
+        services_json(),
+        "$.services[?!match(@.name, '.*-.*')].name",
+        jpl::PathMode::Rfc9535,
+    );
+    assert_eq!(result, json!([42]));
+}
+
+#[test]
+fn test_invalid_regex_is_rejected() {
+    assert!(jpl::from_json(services_json(), "$.services[?(@.name =~ /(/)]").is_null());
+    assert!(jpl::from_json(services_json(), "$.services[?(@.name =~ /a/q)]").is_null());
+    assert!(jpl::from_json(services_json(), "$.services[?(match(@.name, '('))]").is_null());
+}
@@ -0,0 +1,70 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn rfc(json: &str, path: &str) -> serde_json::Value {
+    jpl::from_json_with_mode(json, path, jpl::PathMode::Rfc9535)
+}
+
+#[test]
+fn test_rfc_always_returns_nodelist() {
+    // A single array match is not unwrapped, and no match is an empty nodelist
+    assert_eq!(rfc(r#"{"a":[1,2]}"#, "$.a"), json!([[1, 2]]));
+    assert_eq!(rfc(r#"{"a":[1,2]}"#, "$.missing"), json!([]));
+    assert_eq!(
+        jpl::from_json(r#"{"a":[1,2]}"#, "$.a"),
+        json!([1, 2]),
+        "pragmatic mode stays the default"
+    );
+}
+
+#[test]
+fn test_rfc_negative_index_and_slices() {
+    let json = r#"{"a":[0,1,2,3,4]}"#;
+    assert_eq!(rfc(json, "$.a[-1]"), json!([4]));
+    assert_eq!(rfc(json, "$.a[5:1:-2]"), json!([4, 2]));
+    assert_eq!(rfc(json, "$.a[::-1]"), json!([4, 3, 2, 1, 0]));
+}
+
+#[test]
+fn test_rfc_filter_without_parentheses() {
+    let json = r#"{"a":[{"b":1},{"b":"x"},{"c":2}]}"#;
+    assert_eq!(rfc(json, "$.a[?@.b]"), json!([{"b":1},{"b":"x"}]));
+    assert_eq!(rfc(json, "$.a[?@.b < 2]"), json!([{"b":1}]));
+    // Filters apply to object members, not to the object itself
+    assert_eq!(rfc(r#"{"o":{"x":1,"y":5}}"#, "$.o[?@ > 2]"), json!([5]));
+}
+
+#[test]
+fn test_rfc_comparison_semantics() {
+    let json = r#"{"a":[{"v":1},{"v":"1"},{"v":null},{}]}"#;
+    // No number/string coercion, and a missing member is not null
+    assert_eq!(rfc(json, "$.a[?@.v == 1]"), json!([{"v":1}]));
+    assert_eq!(rfc(json, "$.a[?@.v == null]"), json!([{"v":null}]));
+    assert_eq!(rfc(json, "$.a[?@.v == 1.0e0]"), json!([{"v":1}]));
+}
+
+#[test]
+fn test_rfc_rejects_non_conforming_syntax() {
+    let json = r#"{"a":[1,2,3]}"#;
+    for path in [
+        " $.a",
+        "$.a ",
+        "$.a[01]",
+        "$.a[-0]",
+        "$..",
+        "$.a[?@.*==1]",
+        "$.a[?1]",
+        "$.a[?(lower(@)=='x')]",
+        r#"$['\a']"#,
+    ] {
+        assert!(rfc(json, path).is_null(), "{path} should be rejected");
+    }
+}
+
+#[test]
+fn test_rfc_mode_from_expression() {
+    let expr = r#"from_json("{\"a\":[1,2,3]}", "$.a[-1]", "rfc9535")"#;
+    assert_eq!(jpl::eval(expr).unwrap(), json!([3]));
+    let expr = r#"from_json("{\"a\":[1,2,3]}", "$.a[-1]", "klingon")"#;
+    assert!(jpl::eval(expr).unwrap().is_null());
+}
@@ -0,0 +1,59 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn trace_json() -> &'static str {
+    r#"{
+        "rootSpan": { "id": "s1" },
+        "limits": { "max": 10 },
+        "spans": [
+            { "id": "s1", "parentId": null, "price": 5 },
+            { "id": "s2", "parentId": "s1", "price": 12 },
+            { "id": "s3", "parentId": "s2", "price": 8 }
+        ]
+    }"#
+}
+
+#[test]
+fn test_root_path_compared_with_current_path() {
+    let result = jpl::from_json(trace_json(), "$.spans[?(@.parentId == $.rootSpan.id)].id");
+    assert_eq!(result, json!(["s2"]));
+
+    let result = jpl::from_json(trace_json(), "$.spans[?(@.price < $.limits.max)].id");
+    assert_eq!(result, json!(["s1", "s3"]));
+}
+
+#[test]
+fn test_root_path_supports_full_path_grammar() {
+    let result = jpl::from_json(trace_json(), "$.spans[?(@.id == $.spans[-1].parentId)].id");
+    assert_eq!(result, json!(["s2"]));
+
+    let result = jpl::from_json(
+        trace_json(),
+        "$.spans[?(@.id == $['rootSpan']['id'])].price",
+    );
+    assert_eq!(result, json!([5]));
+}
+
+#[test]
+fn test_root_path_in_rfc_mode() {
+    let result = jpl::from_json_with_mode(
+        trace_json(),
+        "$.spans[?@.price < $.limits.max].id",
+        jpl::PathMode::Rfc9535,
+    );
+    assert_eq!(result, json!(["s1", "s3"]));
+    // Existence test on an absolute query
+    let result = jpl::from_json_with_mode(
+        trace_json(),
+        "$.spans[?$.limits].id",
+        jpl::PathMode::Rfc9535,
+    );
+    assert_eq!(result, json!(["s1", "s2", "s3"]));
+    // Non-singular absolute queries cannot be compared
+    let result = jpl::from_json_with_mode(
+        trace_json(),
+        "$.spans[?@.id == $.spans[*].id]",
+        jpl::PathMode::Rfc9535,
+    );
+    assert!(result.is_null());
+}
@@ -0,0 +1,96 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn spans_json() -> &'static str {
+    r#"{
+        "spans": [
+            { "name": "http.request", "route": "  /users  " },
+            { "name": "db.query", "route": "/orders" },
+            { "name": "http.response", "route": "/ñandú/ø" },
+            { "name": 7, "route": null }
+        ]
+    }"#
+}
+
+fn names(path: &str) -> serde_json::Value {
+    jpl::from_json(spans_json(), path)
+}
+
+#[test]
+fn test_string_predicates() {
+    assert_eq!(
+        names("$.spans[?(starts_with(@.name, 'http.'))].name"),
+        json!(["http.request", "http.response"])
+    );
+    assert_eq!(
+        names("$.spans[?(ends_with(@.name, '.query'))].name"),
+        json!(["db.query"])
+    );
+    assert_eq!(
+        names("$.spans[?(contains(@.name, 'resp'))].name"),
+        json!(["http.response"])
+    );
+    assert_eq!(
+        names("$.spans[?(!starts_with(@.name, 'http.'))].name"),
+        json!(["db.query", 7])
+    );
+}
+
+#[test]
+fn test_trim_and_substring() {
+    assert_eq!(
+        names("$.spans[?(trim(@.route) == '/users')].name"),
+        json!(["http.request"])
+    );
+    assert_eq!(
+        names("$.spans[?(substring(@.name, 0, 4) == 'http')].name"),
+        json!(["http.request", "http.response"])
+    );
+    assert_eq!(
+        names("$.spans[?(substring(@.name, -5) == 'query')].name"),
+        json!(["db.query"])
+    );
+}
+
+#[test]
+fn test_substring_counts_chars() {
+    // Positions are characters, not bytes, as in length()
+    assert_eq!(
+        names("$.spans[?(substring(@.route, 1, 6) == 'ñandú')].name"),
+        json!(["http.response"])
+    );
+    assert_eq!(
+        names("$.spans[?(length(substring(@.route, 1)) == 7)].name"),
+        json!(["http.response"])
+    );
+}
+
+#[test]
+fn test_string_functions_are_not_rfc9535() {
+    let result = jpl::from_json_with_mode(
+        spans_json(),
+        "$.spans[?starts_with(@.name, 'http.')]",
+        jpl::PathMode::Rfc9535,
+    );
+    assert!(result.is_null());
+}
+
+#[test]
+fn test_contains_function_matches_operator() {
+    let json = r#"{"items":[
+        {"id": 1, "tags": ["a", "b"]},
+        {"id": 2, "tags": ["c"]},
+        {"id": 3, "tags": [1.0, 2]},
+        {"id": 4, "tags": "xay"}
+    ]}"#;
+    for (arg, expected) in [
+        ("'a'", json!([1, 4])),
+        ("1", json!([3])),
+        ("'z'", json!(null)),
+    ] {
+        let function = format!("$.items[?(contains(@.tags, {arg}))].id");
+        let operator = format!("$.items[?(@.tags contains {arg})].id");
+        assert_eq!(jpl::from_json(json, &function), expected, "{function}");
+        assert_eq!(jpl::from_json(json, &operator), expected, "{operator}");
+    }
+}
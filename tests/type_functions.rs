@@ -0,0 +1,77 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn attributes_json() -> &'static str {
+    r#"{
+        "attributes": [
+            { "key": "http.method", "value": "GET" },
+            { "key": "http.status_code", "value": 200 },
+            { "key": "peer.service", "value": { "stringValue": "db" } },
+            { "key": "tags", "value": ["a", "b"] },
+            { "key": "retry", "value": true },
+            { "key": "error", "value": null },
+            { "key": "missing" }
+        ]
+    }"#
+}
+
+#[test]
+fn test_type_of() {
+    let result = jpl::from_json(
+        attributes_json(),
+        "$.attributes[?(type_of(@.value) == 'object')].key",
+    );
+    assert_eq!(result, json!(["peer.service"]));
+    let result = jpl::from_json(
+        attributes_json(),
+        "$.attributes[?(type_of(@.value) in ['boolean', 'null'])].key",
+    );
+    assert_eq!(result, json!(["retry", "error"]));
+    // A missing value has no type
+    let result = jpl::from_json(
+        attributes_json(),
+        "$.attributes[?(type_of(@.value) != 'string')].key",
+    );
+    assert_eq!(
+        result,
+        json!([
+            "http.status_code",
+            "peer.service",
+            "tags",
+            "retry",
+            "error",
+            "missing"
+        ])
+    );
+}
+
+#[test]
+fn test_type_predicates() {
+    let result = jpl::from_json(attributes_json(), "$.attributes[?(is_string(@.value))].key");
+    assert_eq!(result, json!(["http.method"]));
+    let result = jpl::from_json(attributes_json(), "$.attributes[?(is_number(@.value))].key");
+    assert_eq!(result, json!(["http.status_code"]));
+    let result = jpl::from_json(
+        attributes_json(),
+        "$.attributes[?(is_array(@.value) || is_boolean(@.value))].key",
+    );
+    assert_eq!(result, json!(["tags", "retry"]));
+    let result = jpl::from_json(
+        attributes_json(),
+        "$.attributes[?(is_object(@.value))].value.stringValue",
+    );
+    assert_eq!(result, json!(["db"]));
+}
+
+#[test]
+fn test_branch_on_type() {
+    // Read a string attribute whether it is stored plainly or wrapped
+    let path = "$.attributes[?(@.key == 'peer.service' && (is_string(@.value) || is_string(@.value.stringValue)))].key";
+    assert_eq!(
+        jpl::from_json(attributes_json(), path),
+        json!(["peer.service"])
+    );
+    // Without coercion, the number 200 and the string '200' are told apart
+    let path = "$.attributes[?(is_string(@.value) && @.value == '200')].key";
+    assert!(jpl::from_json(attributes_json(), path).is_null());
+}
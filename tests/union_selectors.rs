@@ -0,0 +1,34 @@
+use json_path_like_value_retrieval as jpl;
+use serde_json::json;
+
+fn span_json() -> &'static str {
+    r#"{
+        "span": { "traceId": "t-1", "spanId": "s-1", "name": "GET /" },
+        "a": [{"x": 1}, {"y": 2}, {"x": 3}, 3, 4, 5, 6]
+    }"#
+}
+
+#[test]
+fn test_union_of_quoted_keys() {
+    let result = jpl::from_json(span_json(), "$.span['traceId','spanId']");
+    assert_eq!(result, json!(["t-1", "s-1"]));
+}
+
+#[test]
+fn test_union_of_indexes_keeps_order_and_duplicates() {
+    let result = jpl::from_json(r#"{"a":[0,1,2,3]}"#, "$.a[2, 0, 2]");
+    assert_eq!(result, json!([2, 0, 2]));
+}
+
+#[test]
+fn test_union_of_slice_index_and_filter() {
+    let result = jpl::from_json(span_json(), "$.a[0:2, 5, ?(@.x)]");
+    assert_eq!(result, json!([{"x": 1}, {"y": 2}, 5, {"x": 1}, {"x": 3}]));
+}
+
+#[test]
+fn test_union_after_recursive_descent() {
+    let json = r#"{"o":[{"a":1,"b":2},{"a":3,"c":4}]}"#;
+    let result = jpl::from_json(json, "$..['a','b']");
+    assert_eq!(result, json!([1, 2, 3]));
+}